@@ -0,0 +1,140 @@
+//! Attributes of the Star Wars characters that can be compared with Yoda's ones.
+
+use {
+    crate::{
+        birth_year::BirthYear,
+        height::Height,
+        swapi::{Mass, Person},
+        taller::{yoda_birth_year, Character, Relation, YodaTallerError},
+    },
+    std::cmp::Ordering,
+};
+
+/// Attribute to compare two characters by.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Attribute {
+    #[default]
+    Height,
+    Mass,
+    BirthYear,
+}
+
+/// Value of an [`Attribute`] of a character.
+#[derive(Debug, Clone, Copy, serde::Serialize)]
+#[serde(untagged)]
+// derive only for tests
+#[cfg_attr(feature = "test_fixture", derive(serde::Deserialize, PartialEq))]
+pub enum AttributeValue {
+    /// Height in centimeters.
+    Height(Height),
+    /// Mass in kilograms.
+    Mass(Mass),
+    BirthYear(BirthYear),
+}
+
+impl Attribute {
+    /// Value of this attribute for the given person.
+    pub fn of(self, person: &Person) -> Result<AttributeValue, YodaTallerError> {
+        match self {
+            Attribute::Height => person
+                .height
+                .map(AttributeValue::Height)
+                .ok_or(YodaTallerError::HeightNotFound),
+            Attribute::Mass => person
+                .mass
+                .map(AttributeValue::Mass)
+                .ok_or(YodaTallerError::MassNotFound),
+            Attribute::BirthYear => person
+                .birth_year
+                .map(AttributeValue::BirthYear)
+                .ok_or(YodaTallerError::BirthYearNotFound),
+        }
+    }
+
+    /// Value of this attribute for Yoda.
+    pub fn of_yoda(self) -> AttributeValue {
+        match self {
+            Attribute::Height => AttributeValue::Height(Character::yoda().height),
+            Attribute::Mass => AttributeValue::Mass(yoda_mass()),
+            Attribute::BirthYear => AttributeValue::BirthYear(yoda_birth_year()),
+        }
+    }
+
+    /// How a character relates to another one, given the ordering of their values.
+    pub(crate) fn relation(self, ordering: Ordering) -> Relation {
+        match (self, ordering) {
+            (_, Ordering::Equal) => Relation::Equal,
+            (Attribute::Height, Ordering::Greater) => Relation::Taller,
+            (Attribute::Height, Ordering::Less) => Relation::Shorter,
+            (Attribute::Mass, Ordering::Greater) => Relation::Heavier,
+            (Attribute::Mass, Ordering::Less) => Relation::Lighter,
+            // Earlier birth years come first.
+            (Attribute::BirthYear, Ordering::Less) => Relation::Older,
+            (Attribute::BirthYear, Ordering::Greater) => Relation::Younger,
+        }
+    }
+}
+
+/// Yoda weighs 17 kilograms.
+pub fn yoda_mass() -> Mass {
+    Mass::from_kg(17.0)
+}
+
+/// Comparison between Yoda and a person by a given attribute.
+#[derive(Debug, serde::Serialize)]
+// derive only for tests
+#[cfg_attr(feature = "test_fixture", derive(serde::Deserialize, PartialEq))]
+pub struct AttributeComparison {
+    /// Name of the person to compare with Yoda.
+    pub person: String,
+    pub attribute: Attribute,
+    /// How Yoda relates to this character.
+    pub relation: Relation,
+    pub yoda_value: AttributeValue,
+    pub person_value: AttributeValue,
+}
+
+impl AttributeComparison {
+    /// Compare Yoda with the given person.
+    pub fn with_yoda(person: &Person, attribute: Attribute) -> Result<Self, YodaTallerError> {
+        let (yoda_value, person_value, ordering) = match attribute {
+            Attribute::Height => compare(
+                Character::yoda().height,
+                person.height.ok_or(YodaTallerError::HeightNotFound)?,
+                AttributeValue::Height,
+            ),
+            Attribute::Mass => compare(
+                yoda_mass(),
+                person.mass.ok_or(YodaTallerError::MassNotFound)?,
+                AttributeValue::Mass,
+            ),
+            Attribute::BirthYear => compare(
+                yoda_birth_year(),
+                person
+                    .birth_year
+                    .ok_or(YodaTallerError::BirthYearNotFound)?,
+                AttributeValue::BirthYear,
+            ),
+        };
+        Ok(Self {
+            person: person.name.clone(),
+            attribute,
+            relation: attribute.relation(ordering),
+            yoda_value,
+            person_value,
+        })
+    }
+}
+
+/// Values of Yoda and of a person for the same attribute, with their ordering.
+fn compare<T: PartialOrd>(
+    yoda: T,
+    person: T,
+    value: fn(T) -> AttributeValue,
+) -> (AttributeValue, AttributeValue, Ordering) {
+    let ordering = yoda
+        .partial_cmp(&person)
+        .expect("parsed masses and birth years are finite");
+    (value(yoda), value(person), ordering)
+}
@@ -0,0 +1,113 @@
+//! Birth years of the Star Wars characters.
+//!
+//! Swapi counts years from the Battle of Yavin, either before it (`BBY`)
+//! or after it (`ABY`), such as `896BBY` for Yoda or `19BBY` for Luke.
+
+use std::{cmp::Ordering, fmt, str::FromStr};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Era {
+    /// Before the Battle of Yavin.
+    Bby,
+    /// After the Battle of Yavin.
+    Aby,
+}
+
+impl Era {
+    fn suffix(self) -> &'static str {
+        match self {
+            Era::Bby => "BBY",
+            Era::Aby => "ABY",
+        }
+    }
+}
+
+/// Year a character was born in.
+/// Earlier years are smaller, so that older characters come first.
+#[derive(Debug, Clone, Copy, serde::Serialize)]
+// derive only for tests
+#[cfg_attr(feature = "test_fixture", derive(serde::Deserialize))]
+#[serde(into = "String", try_from = "String")]
+pub struct BirthYear {
+    /// Years between the birth and the Battle of Yavin, such as `41.9`.
+    years: f64,
+    era: Era,
+}
+
+impl BirthYear {
+    pub fn new(years: f64, era: Era) -> Self {
+        Self { years, era }
+    }
+
+    /// Years since the Battle of Yavin, negative if born before it.
+    pub fn years_after_battle(self) -> f64 {
+        match self.era {
+            Era::Bby => -self.years,
+            Era::Aby => self.years,
+        }
+    }
+}
+
+impl PartialEq for BirthYear {
+    /// Years are equal if they are as far from the battle, so that `0BBY` equals `0ABY`.
+    fn eq(&self, other: &Self) -> bool {
+        self.years_after_battle() == other.years_after_battle()
+    }
+}
+
+impl PartialOrd for BirthYear {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.years_after_battle()
+            .partial_cmp(&other.years_after_battle())
+    }
+}
+
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum ParseBirthYearError {
+    #[error("missing BBY or ABY era")]
+    MissingEra,
+    #[error("invalid number of years")]
+    InvalidYears,
+}
+
+impl FromStr for BirthYear {
+    type Err = ParseBirthYearError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let (years, era) = if let Some(years) = s.strip_suffix(Era::Bby.suffix()) {
+            (years, Era::Bby)
+        } else if let Some(years) = s.strip_suffix(Era::Aby.suffix()) {
+            (years, Era::Aby)
+        } else {
+            return Err(ParseBirthYearError::MissingEra);
+        };
+        let years: f64 = years
+            .parse()
+            .map_err(|_| ParseBirthYearError::InvalidYears)?;
+        if !years.is_finite() || years < 0.0 {
+            return Err(ParseBirthYearError::InvalidYears);
+        }
+        Ok(Self { years, era })
+    }
+}
+
+impl fmt::Display for BirthYear {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.years, self.era.suffix())
+    }
+}
+
+impl From<BirthYear> for String {
+    fn from(birth_year: BirthYear) -> Self {
+        birth_year.to_string()
+    }
+}
+
+impl TryFrom<String> for BirthYear {
+    type Error = ParseBirthYearError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
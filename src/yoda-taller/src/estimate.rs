@@ -0,0 +1,67 @@
+use {
+    crate::{
+        height::Height,
+        swapi::{Person, SwapiClient},
+        taller::YodaTallerError,
+    },
+    futures::future::try_join_all,
+};
+
+/// How to estimate the height of a person when Swapi doesn't know it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Estimate {
+    /// Use the average height of the person's species.
+    Species,
+}
+
+/// What an estimated height is based on.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+#[serde(tag = "source", rename_all = "snake_case")]
+// derive only for tests
+#[cfg_attr(feature = "test_fixture", derive(serde::Deserialize))]
+pub enum EstimateBasis {
+    /// The average height of the species, as reported by Swapi.
+    AverageHeight { species: String },
+    /// The average height of the members of the species whose height is known.
+    Members { species: String, members: usize },
+}
+
+/// Estimate the height of the person from the average height of their species.
+///
+/// If Swapi doesn't report an average height for the species,
+/// it is computed from the members of the species.
+pub async fn species_height(
+    swapi_client: &SwapiClient,
+    person: &Person,
+) -> Result<(Height, EstimateBasis), YodaTallerError> {
+    let species_url = person.species.first().ok_or_else(|| {
+        tracing::warn!("unknown species, cannot estimate height");
+        YodaTallerError::HeightNotFound
+    })?;
+    let species = swapi_client.species(species_url).await?;
+    if let Some(average_height) = species.average_height {
+        let basis = EstimateBasis::AverageHeight {
+            species: species.name,
+        };
+        return Ok((average_height, basis));
+    }
+
+    let members = try_join_all(species.people.iter().map(|url| swapi_client.person(url))).await?;
+    let heights: Vec<u32> = members
+        .iter()
+        .filter_map(|member| member.height)
+        .map(|height| height.cm())
+        .collect();
+    if heights.is_empty() {
+        tracing::warn!("no member of {} has a known height", species.name);
+        return Err(YodaTallerError::HeightNotFound);
+    }
+    let total: u32 = heights.iter().sum();
+    let average = (f64::from(total) / heights.len() as f64).round() as u32;
+    let basis = EstimateBasis::Members {
+        species: species.name,
+        members: heights.len(),
+    };
+    Ok((Height::from_cm(average), basis))
+}
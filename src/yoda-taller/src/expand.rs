@@ -0,0 +1,112 @@
+use {
+    crate::swapi::{Film, Person, Planet, Species, SwapiClient},
+    futures::future::{try_join_all, OptionFuture},
+    std::str::FromStr,
+};
+
+/// Resource linked from a person that can be embedded in a response.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Link {
+    Homeworld,
+    Species,
+    Films,
+}
+
+impl FromStr for Link {
+    type Err = ParseLinkError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "homeworld" => Ok(Self::Homeworld),
+            "species" => Ok(Self::Species),
+            "films" => Ok(Self::Films),
+            _ => Err(ParseLinkError(s.to_string())),
+        }
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+#[error("unknown link `{0}`, expected one of `homeworld`, `species`, `films`")]
+pub struct ParseLinkError(String);
+
+/// Comma separated list of links to resolve, such as `homeworld,species`.
+#[derive(Debug, Default, Clone, PartialEq, Eq, serde::Deserialize)]
+#[serde(try_from = "String")]
+pub struct Links(Vec<Link>);
+
+impl Links {
+    pub fn contains(&self, link: Link) -> bool {
+        self.0.contains(&link)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl FromStr for Links {
+    type Err = ParseLinkError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let links = s
+            .split(',')
+            .map(str::trim)
+            .filter(|link| !link.is_empty())
+            .map(Link::from_str)
+            .collect::<Result<_, _>>()?;
+        Ok(Self(links))
+    }
+}
+
+impl TryFrom<String> for Links {
+    type Error = ParseLinkError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+/// Resources linked from a person, resolved from Swapi.
+/// Only the requested links are present.
+#[derive(Debug, Default, serde::Serialize)]
+// derive only for tests
+#[cfg_attr(feature = "test_fixture", derive(serde::Deserialize, PartialEq))]
+pub struct Expanded {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub homeworld: Option<Planet>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub species: Option<Vec<Species>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub films: Option<Vec<Film>>,
+}
+
+impl Expanded {
+    /// Fetch the given links of the person.
+    ///
+    /// All the resources are fetched concurrently, so the whole expansion
+    /// takes at most the timeout of the Swapi client.
+    pub async fn resolve(
+        swapi_client: &SwapiClient,
+        person: &Person,
+        links: &Links,
+    ) -> Result<Self, reqwest::Error> {
+        let homeworld: OptionFuture<_> = links
+            .contains(Link::Homeworld)
+            .then(|| swapi_client.planet(&person.homeworld))
+            .into();
+        let species: OptionFuture<_> = links
+            .contains(Link::Species)
+            .then(|| try_join_all(person.species.iter().map(|url| swapi_client.species(url))))
+            .into();
+        let films: OptionFuture<_> = links
+            .contains(Link::Films)
+            .then(|| try_join_all(person.films.iter().map(|url| swapi_client.film(url))))
+            .into();
+        let (homeworld, species, films) = tokio::join!(homeworld, species, films);
+        Ok(Self {
+            homeworld: homeworld.transpose()?,
+            species: species.transpose()?,
+            films: films.transpose()?,
+        })
+    }
+}
@@ -0,0 +1,81 @@
+//! Heights of the Star Wars characters and the units to display them.
+
+use std::{fmt, num::ParseIntError, str::FromStr};
+
+/// Height of a character, stored in centimeters as Swapi does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, serde::Serialize)]
+#[serde(transparent)]
+// derive only for tests
+#[cfg_attr(feature = "test_fixture", derive(serde::Deserialize))]
+pub struct Height {
+    cm: u32,
+}
+
+impl Height {
+    pub const fn from_cm(cm: u32) -> Self {
+        Self { cm }
+    }
+
+    pub fn cm(self) -> u32 {
+        self.cm
+    }
+}
+
+impl fmt::Display for Height {
+    /// Display the height in centimeters, as Swapi does.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.cm.fmt(f)
+    }
+}
+
+impl FromStr for Height {
+    type Err = ParseIntError;
+
+    /// Parse a height in centimeters, such as `"172"`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse().map(Self::from_cm)
+    }
+}
+
+/// Unit of length used to display heights.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Unit {
+    #[default]
+    Cm,
+    M,
+    In,
+    Ft,
+}
+
+impl Unit {
+    /// Convert a length in centimeters to this unit.
+    pub fn convert_cm(self, cm: f64) -> f64 {
+        match self {
+            Unit::Cm => cm,
+            Unit::M => cm / 100.0,
+            Unit::In => cm / 2.54,
+            Unit::Ft => cm / 30.48,
+        }
+    }
+
+    /// Format a length in centimeters in this unit, e.g. `1.72 m`.
+    pub fn format_cm(self, cm: f64) -> String {
+        match self {
+            Unit::Cm => format!("{cm} {self}"),
+            _ => format!("{:.2} {self}", self.convert_cm(cm)),
+        }
+    }
+}
+
+impl fmt::Display for Unit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let symbol = match self {
+            Unit::Cm => "cm",
+            Unit::M => "m",
+            Unit::In => "in",
+            Unit::Ft => "ft",
+        };
+        f.write_str(symbol)
+    }
+}
@@ -1,5 +1,13 @@
+pub mod attribute;
+pub mod birth_year;
+pub mod estimate;
+pub mod expand;
+pub mod height;
+pub mod ranking;
 pub mod server;
 pub mod settings;
+pub mod source;
+pub mod stats;
 pub mod swapi;
 pub mod taller;
 pub mod traces;
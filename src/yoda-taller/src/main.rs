@@ -9,7 +9,11 @@ async fn main() -> anyhow::Result<()> {
 
     let settings = settings()?;
 
-    Application::bind(settings)?.run().await?;
+    match std::env::args().nth(1).as_deref() {
+        None | Some("serve") => Application::bind(settings)?.run().await?,
+        Some("sync") => sync(&settings).await?,
+        Some(command) => anyhow::bail!("unknown command `{command}`, expected `serve` or `sync`"),
+    }
 
     opentelemetry::global::shutdown_tracer_provider();
 
@@ -22,3 +26,15 @@ fn settings() -> anyhow::Result<Settings> {
     let settings = Settings::read(&config_file).context("cannot read settings")?;
     Ok(settings)
 }
+
+/// Copy the people of Swapi into the people database.
+async fn sync(settings: &Settings) -> anyhow::Result<()> {
+    let mirror = settings.swapi.sqlite_mirror()?;
+    let swapi_client = settings.swapi.swapi_client()?;
+    let report = mirror.sync(&swapi_client).await?;
+    println!(
+        "{} people added, {} updated, {} unchanged",
+        report.added, report.updated, report.unchanged
+    );
+    Ok(())
+}
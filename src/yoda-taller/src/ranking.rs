@@ -0,0 +1,91 @@
+//! Rank the people or films returned by a Swapi search by how well their name
+//! or title matches the query.
+//!
+//! Swapi matches any name containing the searched text, so that a search for
+//! `yoda` could return any character with `yoda` in their name.
+
+use crate::swapi::{Film, Person};
+
+/// How strictly a name must match the query.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MatchMode {
+    /// The name must be equal to the query, ignoring case.
+    Exact,
+    /// The name must start with the query, ignoring case.
+    Prefix,
+    /// The name must contain the query, as Swapi does.
+    #[default]
+    Any,
+}
+
+/// How well a name matches a query. Better matches come first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Rank {
+    Exact,
+    Prefix,
+    Other,
+}
+
+impl Rank {
+    pub fn of(name: &str, query: &str) -> Self {
+        let name = name.to_lowercase();
+        let query = query.trim().to_lowercase();
+        if name == query {
+            Rank::Exact
+        } else if name.starts_with(&query) {
+            Rank::Prefix
+        } else {
+            Rank::Other
+        }
+    }
+
+    fn is_allowed_by(self, mode: MatchMode) -> bool {
+        match mode {
+            MatchMode::Exact => self == Rank::Exact,
+            MatchMode::Prefix => self <= Rank::Prefix,
+            MatchMode::Any => true,
+        }
+    }
+}
+
+/// A Swapi resource searched by name, such as a person, or by title, such as a film.
+pub trait Named {
+    /// The name or title matched against the query.
+    fn name(&self) -> &str;
+}
+
+impl Named for Person {
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl Named for Film {
+    fn name(&self) -> &str {
+        &self.title
+    }
+}
+
+/// Resources allowed by the match mode, sorted from the best match to the worst one.
+/// Resources with the same rank keep the order returned by Swapi.
+pub fn rank_matches<T: Named>(query: &str, resources: Vec<T>, mode: MatchMode) -> Vec<(Rank, T)> {
+    let mut ranked: Vec<(Rank, T)> = resources
+        .into_iter()
+        .map(|resource| (Rank::of(resource.name(), query), resource))
+        .filter(|(rank, _)| rank.is_allowed_by(mode))
+        .collect();
+    ranked.sort_by_key(|(rank, _)| *rank);
+    ranked
+}
+
+/// The resources that match the query best.
+pub fn best_matches<T: Named>(query: &str, resources: Vec<T>, mode: MatchMode) -> Vec<T> {
+    let ranked = rank_matches(query, resources, mode);
+    let best_rank = ranked.first().map(|(rank, _)| *rank);
+    ranked
+        .into_iter()
+        .take_while(|(rank, _)| Some(*rank) == best_rank)
+        .map(|(_, resource)| resource)
+        .collect()
+}
@@ -0,0 +1,24 @@
+use {
+    crate::{source::CacheStats, taller::YodaTaller},
+    axum::{Extension, Json},
+    std::sync::Arc,
+};
+
+#[derive(Debug, serde::Serialize)]
+// derive only for tests
+#[cfg_attr(feature = "test_fixture", derive(serde::Deserialize, PartialEq, Eq))]
+pub struct CacheResponse {
+    /// Whether people searches are cached.
+    pub enabled: bool,
+    /// Cache counters, if the cache is enabled.
+    #[serde(flatten)]
+    pub stats: Option<CacheStats>,
+}
+
+pub async fn cache(Extension(yoda_taller): Extension<Arc<YodaTaller>>) -> Json<CacheResponse> {
+    let stats = yoda_taller.cache_stats();
+    Json(CacheResponse {
+        enabled: stats.is_some(),
+        stats,
+    })
+}
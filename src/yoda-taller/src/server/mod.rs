@@ -1,3 +1,5 @@
+pub mod admin_route;
 mod shutdown;
 pub mod startup;
+pub mod stats_route;
 pub mod taller_route;
@@ -1,8 +1,17 @@
 use {
     super::shutdown::shutdown_handler,
-    crate::{server::taller_route, settings::Settings},
+    crate::{
+        server::{admin_route, stats_route, taller_route},
+        settings::Settings,
+        source::CircuitState,
+        stats::HeightStatsCache,
+        taller::YodaTaller,
+    },
     anyhow::Context,
-    axum::{routing::get, Extension, Router},
+    axum::{
+        routing::{get, post},
+        Extension, Json, Router,
+    },
     axum_tracing_opentelemetry::opentelemetry_tracing_layer,
     std::{
         io,
@@ -31,19 +40,50 @@ impl Application {
             let yoda_taller = self.settings.swapi.yoda_taller()?;
             Arc::new(yoda_taller)
         };
-        // build our application with a single route
+        let height_stats = Arc::new(HeightStatsCache::default());
+        let stats_refresh = tokio::spawn(Arc::clone(&height_stats).refresh_periodically(
+            Arc::clone(&yoda_taller),
+            self.settings.stats.bucket_width_cm,
+            self.settings.stats.refresh_interval(),
+        ));
+        // build our application with its routes
         let app = Router::new()
             .route("/health_check", get(health_check))
+            .route("/taller", post(taller_route::batch_taller_than))
+            .route("/taller/stream", post(taller_route::stream_taller_than))
             .route("/taller/:name", get(taller_route::taller_than))
+            .route("/older/:name", get(taller_route::older_than))
+            // Routes sharing a prefix must use the same name for the shared parameter.
+            .route("/compare/:first", get(taller_route::compare_by))
+            .route("/compare/:first/:second", get(taller_route::compare))
+            .route(
+                "/films/:title/shorter-than-yoda",
+                get(taller_route::shorter_than_yoda),
+            )
+            .route("/rank", get(taller_route::rank))
+            .route("/matrix", post(taller_route::matrix))
+            .route("/stats/heights", get(stats_route::heights))
+            .route("/admin/cache", get(admin_route::cache))
             .layer(Extension(yoda_taller))
+            .layer(Extension(height_stats))
+            .layer(Extension(taller_route::BatchConcurrency(
+                self.settings.application.batch_concurrency,
+            )))
+            .layer(Extension(taller_route::MaxRankNames(
+                self.settings.application.max_rank_names,
+            )))
+            .layer(Extension(taller_route::MaxMatrixNames(
+                self.settings.application.max_matrix_names,
+            )))
             .layer(opentelemetry_tracing_layer());
 
-        axum::Server::from_tcp(self.tcp_listener)
+        let served = axum::Server::from_tcp(self.tcp_listener)
             .context("cannot build server")?
             .serve(app.into_make_service())
             .with_graceful_shutdown(shutdown_handler())
-            .await
-            .context("cannot run server")?;
+            .await;
+        stats_refresh.abort();
+        served.context("cannot run server")?;
 
         Ok(())
     }
@@ -53,4 +93,19 @@ impl Application {
     }
 }
 
-pub async fn health_check() {}
+#[derive(Debug, serde::Serialize)]
+// derive only for tests
+#[cfg_attr(feature = "test_fixture", derive(serde::Deserialize, PartialEq, Eq))]
+pub struct HealthResponse {
+    /// State of the circuit breaker in front of Swapi, if people are searched there.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub swapi_circuit: Option<CircuitState>,
+}
+
+pub async fn health_check(
+    Extension(yoda_taller): Extension<Arc<YodaTaller>>,
+) -> Json<HealthResponse> {
+    Json(HealthResponse {
+        swapi_circuit: yoda_taller.circuit_state(),
+    })
+}
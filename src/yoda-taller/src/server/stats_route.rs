@@ -0,0 +1,38 @@
+use {
+    crate::stats::{HeightStats, HeightStatsCache},
+    axum::{http::StatusCode, response::IntoResponse, Extension, Json},
+    serde::Serialize,
+    std::sync::Arc,
+};
+
+/// Statistics about the heights of all the people known by Swapi,
+/// as of the latest background refresh.
+pub async fn heights(
+    Extension(height_stats): Extension<Arc<HeightStatsCache>>,
+) -> Result<Json<HeightStats>, StatsNotReady> {
+    height_stats.latest().map(Json).ok_or(StatsNotReady)
+}
+
+/// The statistics haven't been computed yet.
+#[derive(Debug)]
+pub struct StatsNotReady;
+
+impl IntoResponse for StatsNotReady {
+    fn into_response(self) -> axum::response::Response {
+        (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(StatsErrorBody {
+                error: "Height statistics are not computed yet".to_string(),
+            }),
+        )
+            .into_response()
+    }
+}
+
+#[derive(Debug, Serialize)]
+// derive deserialize only on tests
+#[cfg_attr(feature = "test_fixture", derive(serde::Deserialize, PartialEq, Eq))]
+pub struct StatsErrorBody {
+    /// Error message
+    pub error: String,
+}
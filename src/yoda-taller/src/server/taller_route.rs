@@ -1,14 +1,35 @@
 use {
-    crate::taller::{YodaTaller, YodaTallerError, YodaTallerOutcome},
-    axum::{extract::Path, http::StatusCode, response::IntoResponse, Extension, Json},
+    crate::{
+        attribute::{Attribute, AttributeComparison},
+        estimate::Estimate,
+        expand::{Expanded, Links},
+        height::{Height, Unit},
+        taller::{
+            Candidate, Character, Comparison, ComparisonError, FilmComparison, HeightMatrix,
+            HeightRanking, LookupOptions, RankedCharacter, Relation, YodaOlderOutcome, YodaTaller,
+            YodaTallerError, YodaTallerOutcome,
+        },
+    },
+    axum::{
+        body::StreamBody,
+        extract::{
+            rejection::{JsonRejection, QueryRejection},
+            Path, Query,
+        },
+        http::{header, HeaderMap, HeaderValue, StatusCode},
+        response::{IntoResponse, Response},
+        Extension, Json,
+    },
+    chrono::{DateTime, Utc},
+    futures::{stream, StreamExt},
     serde::Serialize,
-    std::sync::Arc,
+    std::{sync::Arc, time::Duration},
     tracing::{error, warn},
 };
 
 #[derive(Debug, serde::Serialize)]
 // derive only for tests
-#[cfg_attr(feature = "test_fixture", derive(serde::Deserialize, PartialEq, Eq))]
+#[cfg_attr(feature = "test_fixture", derive(serde::Deserialize, PartialEq))]
 pub struct YodaTallerResponse {
     /// Name to identify a person.
     /// Parameter originally sent from the user.
@@ -16,6 +37,59 @@ pub struct YodaTallerResponse {
     /// Query result.
     #[serde(flatten)]
     pub result: YodaTallerOutcome,
+    /// Heights rendered in the unit requested by the user, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display: Option<HeightsDisplay>,
+    /// Resources linked from the person, if the user asked to expand them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expanded: Option<Expanded>,
+    /// When the people were last copied from Swapi, if they are served from a local copy.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub synced_at: Option<DateTime<Utc>>,
+    /// Whether the person is an expired copy, served because Swapi failed.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub stale: bool,
+}
+
+/// Heights of a [`YodaTallerOutcome`], formatted in a given unit.
+#[derive(Debug, serde::Serialize)]
+// derive only for tests
+#[cfg_attr(feature = "test_fixture", derive(serde::Deserialize, PartialEq, Eq))]
+pub struct HeightsDisplay {
+    /// Unit the heights are rendered in.
+    pub units: Unit,
+    /// Yoda's height, such as `"66 cm"`.
+    pub yoda_height: String,
+    /// Height of the person.
+    pub person_height: String,
+    /// How much Yoda is taller than the person.
+    /// Negative if Yoda is shorter.
+    pub difference: String,
+}
+
+impl HeightsDisplay {
+    pub fn new(outcome: &YodaTallerOutcome, units: Unit) -> Self {
+        Self {
+            units,
+            yoda_height: units.format_cm(f64::from(outcome.yoda_height.cm())),
+            person_height: units.format_cm(f64::from(outcome.person_height.cm())),
+            difference: units.format_cm(outcome.difference_cm as f64),
+        }
+    }
+}
+
+/// Query parameters of the taller route.
+#[derive(Debug, Default, serde::Deserialize)]
+pub struct TallerParams {
+    #[serde(flatten)]
+    pub lookup: LookupOptions,
+    /// Unit to display heights in.
+    pub units: Option<Unit>,
+    /// Links of the person to resolve, such as `homeworld,species`.
+    #[serde(default)]
+    pub expand: Links,
+    /// How to estimate the height of the person, if Swapi doesn't know it.
+    pub estimate: Option<Estimate>,
 }
 
 #[derive(Debug)]
@@ -27,69 +101,691 @@ pub struct YodaTallerResponseError {
     pub error: YodaTallerError,
 }
 
+/// Invalid query parameters sent by the user.
+#[derive(Debug)]
+pub struct InvalidParamsError {
+    /// Name to identify a person.
+    /// Parameter originally sent from the user.
+    pub query: String,
+    pub rejection: QueryRejection,
+}
+
+/// Invalid request body sent by the user.
+#[derive(Debug)]
+pub struct InvalidBodyError {
+    pub rejection: JsonRejection,
+}
+
 pub async fn taller_than(
     Path(person_name): Path<String>,
+    params: Result<Query<TallerParams>, QueryRejection>,
     Extension(yoda_taller): Extension<Arc<YodaTaller>>,
-) -> Result<Json<YodaTallerResponse>, YodaTallerResponseError> {
-    match yoda_taller.is_taller_than(&person_name).await {
-        Ok(result) => {
+) -> Result<(HeaderMap, Json<YodaTallerResponse>), Response> {
+    let params = query_params(&person_name, params).map_err(IntoResponse::into_response)?;
+    match yoda_taller
+        .is_taller_than_with(
+            &person_name,
+            &params.lookup,
+            &params.expand,
+            params.estimate,
+        )
+        .await
+    {
+        Ok(lookup) => {
             let json_response = YodaTallerResponse {
+                query: person_name,
+                display: params
+                    .units
+                    .map(|units| HeightsDisplay::new(&lookup.outcome, units)),
+                result: lookup.outcome,
+                expanded: lookup.expanded,
+                synced_at: yoda_taller.synced_at().await,
+                stale: lookup.stale_for.is_some(),
+            }
+            .into();
+            Ok((stale_headers(lookup.stale_for), json_response))
+        }
+        Err(e) => {
+            log_error(&e);
+            let error = YodaTallerResponseError {
+                query: person_name,
+                error: e,
+            };
+            Err(error.into_response())
+        }
+    }
+}
+
+/// Maximum number of names of a batch request looked up at the same time.
+#[derive(Debug, Clone, Copy)]
+pub struct BatchConcurrency(pub usize);
+
+/// Maximum number of names of a rank request.
+#[derive(Debug, Clone, Copy)]
+pub struct MaxRankNames(pub usize);
+
+/// Maximum number of names of a matrix request.
+#[derive(Debug, Clone, Copy)]
+pub struct MaxMatrixNames(pub usize);
+
+/// Request with more names than allowed.
+#[derive(Debug)]
+pub struct TooManyNamesError {
+    /// Names sent by the user.
+    pub query: String,
+    /// Maximum number of names allowed.
+    pub max: usize,
+}
+
+/// Result for one of the names of a batch request.
+#[derive(Debug, serde::Serialize)]
+#[serde(untagged)]
+// derive only for tests
+#[cfg_attr(feature = "test_fixture", derive(serde::Deserialize, PartialEq))]
+pub enum BatchEntry {
+    Ok(Box<YodaTallerResponse>),
+    Error(ErrorBody),
+}
+
+/// Is Yoda taller than each of the people with the given names?
+/// Results are in the same order as the names. A name that can't be compared
+/// doesn't fail the whole batch, but results in an error entry.
+pub async fn batch_taller_than(
+    params: Result<Query<LookupOptions>, QueryRejection>,
+    Extension(yoda_taller): Extension<Arc<YodaTaller>>,
+    Extension(BatchConcurrency(concurrency)): Extension<BatchConcurrency>,
+    names: Result<Json<Vec<String>>, JsonRejection>,
+) -> Result<Json<Vec<BatchEntry>>, Response> {
+    let names = json_body(names).map_err(IntoResponse::into_response)?;
+    let options = query_params(&names.join(","), params).map_err(IntoResponse::into_response)?;
+    let synced_at = yoda_taller.synced_at().await;
+    let entries = stream::iter(names)
+        .map(|person_name| batch_entry(&yoda_taller, person_name, &options, synced_at))
+        .buffered(concurrency.max(1))
+        .collect()
+        .await;
+    Ok(Json(entries))
+}
+
+/// Order of the results of a streamed batch.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ResultOrder {
+    /// Stream each result as soon as its lookup completes.
+    #[default]
+    Completion,
+    /// Stream the results in the same order as the names.
+    Input,
+}
+
+/// Query parameters of the streamed batch route.
+#[derive(Debug, Default, serde::Deserialize)]
+pub struct StreamParams {
+    #[serde(flatten)]
+    pub lookup: LookupOptions,
+    #[serde(default)]
+    pub order: ResultOrder,
+}
+
+/// Is Yoda taller than each of the people with the given names, one per line?
+/// Results are streamed as newline-delimited JSON, one [`BatchEntry`] per line.
+pub async fn stream_taller_than(
+    params: Result<Query<StreamParams>, QueryRejection>,
+    Extension(yoda_taller): Extension<Arc<YodaTaller>>,
+    Extension(BatchConcurrency(concurrency)): Extension<BatchConcurrency>,
+    names: String,
+) -> Result<impl IntoResponse, Response> {
+    let names: Vec<String> = names
+        .lines()
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(str::to_string)
+        .collect();
+    let params = query_params(&names.join(","), params).map_err(IntoResponse::into_response)?;
+    let options = params.lookup;
+    let synced_at = yoda_taller.synced_at().await;
+    let lookups = stream::iter(names).map(move |person_name| {
+        let (yoda_taller, options) = (Arc::clone(&yoda_taller), options.clone());
+        async move { batch_entry(&yoda_taller, person_name, &options, synced_at).await }
+    });
+    let concurrency = concurrency.max(1);
+    let entries = match params.order {
+        ResultOrder::Completion => lookups.buffer_unordered(concurrency).boxed(),
+        ResultOrder::Input => lookups.buffered(concurrency).boxed(),
+    };
+    let lines = entries.map(|entry| {
+        let mut line = serde_json::to_vec(&entry)?;
+        line.push(b'\n');
+        Ok::<_, serde_json::Error>(line)
+    });
+    Ok((
+        [(header::CONTENT_TYPE, "application/x-ndjson")],
+        StreamBody::new(lines),
+    ))
+}
+
+/// Headers telling that the response is stale, as it was retrieved `stale_for` ago.
+fn stale_headers(stale_for: Option<Duration>) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    if let Some(age) = stale_for {
+        headers.insert(header::AGE, HeaderValue::from(age.as_secs()));
+        headers.insert(
+            header::WARNING,
+            HeaderValue::from_static("110 - \"Response is Stale\""),
+        );
+    }
+    headers
+}
+
+/// Look up one of the names of a batch request.
+/// `synced_at` is read once for the whole batch, rather than once per name.
+async fn batch_entry(
+    yoda_taller: &YodaTaller,
+    person_name: String,
+    options: &LookupOptions,
+    synced_at: Option<DateTime<Utc>>,
+) -> BatchEntry {
+    let lookup = yoda_taller
+        .is_taller_than_with(&person_name, options, &Links::default(), None)
+        .await;
+    match lookup {
+        Ok(lookup) => BatchEntry::Ok(Box::new(YodaTallerResponse {
+            query: person_name,
+            result: lookup.outcome,
+            display: None,
+            expanded: None,
+            synced_at,
+            stale: lookup.stale_for.is_some(),
+        })),
+        Err(e) => {
+            log_error(&e);
+            BatchEntry::Error(
+                YodaTallerResponseError {
+                    query: person_name,
+                    error: e,
+                }
+                .into(),
+            )
+        }
+    }
+}
+
+#[derive(Debug, serde::Serialize)]
+// derive only for tests
+#[cfg_attr(feature = "test_fixture", derive(serde::Deserialize, PartialEq))]
+pub struct YodaOlderResponse {
+    /// Name to identify a person.
+    /// Parameter originally sent from the user.
+    pub query: String,
+    /// Query result.
+    #[serde(flatten)]
+    pub result: YodaOlderOutcome,
+    /// When the people were last copied from Swapi, if they are served from a local copy.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub synced_at: Option<DateTime<Utc>>,
+}
+
+pub async fn older_than(
+    Path(person_name): Path<String>,
+    params: Result<Query<LookupOptions>, QueryRejection>,
+    Extension(yoda_taller): Extension<Arc<YodaTaller>>,
+) -> Result<Json<YodaOlderResponse>, Response> {
+    let options = query_params(&person_name, params).map_err(IntoResponse::into_response)?;
+    match yoda_taller.is_older_than(&person_name, &options).await {
+        Ok(result) => {
+            let json_response = YodaOlderResponse {
                 query: person_name,
                 result,
+                synced_at: yoda_taller.synced_at().await,
             }
             .into();
             Ok(json_response)
         }
         Err(e) => {
             log_error(&e);
-            Err(YodaTallerResponseError {
+            let error = YodaTallerResponseError {
                 query: person_name,
                 error: e,
+            };
+            Err(error.into_response())
+        }
+    }
+}
+
+/// Extract the query parameters, replying with `400 Bad Request` if they are invalid.
+fn query_params<T>(
+    person_name: &str,
+    params: Result<Query<T>, QueryRejection>,
+) -> Result<T, InvalidParamsError> {
+    params.map(|Query(params)| params).map_err(|rejection| {
+        warn!("{rejection}");
+        InvalidParamsError {
+            query: person_name.to_string(),
+            rejection,
+        }
+    })
+}
+
+/// Extract the JSON body, replying with an error if it is invalid.
+fn json_body<T>(body: Result<Json<T>, JsonRejection>) -> Result<T, InvalidBodyError> {
+    body.map(|Json(body)| body).map_err(|rejection| {
+        warn!("{rejection}");
+        InvalidBodyError { rejection }
+    })
+}
+
+#[derive(Debug, serde::Serialize)]
+// derive only for tests
+#[cfg_attr(feature = "test_fixture", derive(serde::Deserialize, PartialEq))]
+pub struct AttributeComparisonResponse {
+    /// Name to identify a person.
+    /// Parameter originally sent from the user.
+    pub query: String,
+    /// Comparison result.
+    #[serde(flatten)]
+    pub result: AttributeComparison,
+    /// When the people were last copied from Swapi, if they are served from a local copy.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub synced_at: Option<DateTime<Utc>>,
+}
+
+/// Query parameters of the route comparing Yoda by attribute.
+#[derive(Debug, Default, serde::Deserialize)]
+pub struct CompareByParams {
+    #[serde(flatten)]
+    pub lookup: LookupOptions,
+    /// Attribute to compare by.
+    #[serde(default)]
+    pub by: Attribute,
+}
+
+pub async fn compare_by(
+    Path(person_name): Path<String>,
+    params: Result<Query<CompareByParams>, QueryRejection>,
+    Extension(yoda_taller): Extension<Arc<YodaTaller>>,
+) -> Result<Json<AttributeComparisonResponse>, Response> {
+    let params = query_params(&person_name, params).map_err(IntoResponse::into_response)?;
+    match yoda_taller
+        .compare_with_yoda(&person_name, params.by, &params.lookup)
+        .await
+    {
+        Ok(result) => {
+            let json_response = AttributeComparisonResponse {
+                query: person_name,
+                result,
+                synced_at: yoda_taller.synced_at().await,
+            }
+            .into();
+            Ok(json_response)
+        }
+        Err(e) => {
+            log_error(&e);
+            let error = YodaTallerResponseError {
+                query: person_name,
+                error: e,
+            };
+            Err(error.into_response())
+        }
+    }
+}
+
+#[derive(Debug, serde::Serialize)]
+// derive only for tests
+#[cfg_attr(feature = "test_fixture", derive(serde::Deserialize, PartialEq, Eq))]
+pub struct CompareResponse {
+    /// Names to identify the two people.
+    /// Parameters originally sent from the user.
+    pub queries: [String; 2],
+    /// Comparison result.
+    #[serde(flatten)]
+    pub result: Comparison,
+    /// When the people were last copied from Swapi, if they are served from a local copy.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub synced_at: Option<DateTime<Utc>>,
+}
+
+pub async fn compare(
+    Path((first, second)): Path<(String, String)>,
+    params: Result<Query<LookupOptions>, QueryRejection>,
+    Extension(yoda_taller): Extension<Arc<YodaTaller>>,
+) -> Result<Json<CompareResponse>, Response> {
+    let options =
+        query_params(&format!("{first},{second}"), params).map_err(IntoResponse::into_response)?;
+    match yoda_taller.compare(&first, &second, &options).await {
+        Ok(result) => {
+            let json_response = CompareResponse {
+                queries: [first, second],
+                result,
+                synced_at: yoda_taller.synced_at().await,
+            }
+            .into();
+            Ok(json_response)
+        }
+        Err(ComparisonError { query, error }) => {
+            log_error(&error);
+            Err(YodaTallerResponseError { query, error }.into_response())
+        }
+    }
+}
+
+#[derive(Debug, serde::Serialize)]
+// derive only for tests
+#[cfg_attr(feature = "test_fixture", derive(serde::Deserialize, PartialEq, Eq))]
+pub struct ShorterThanYodaResponse {
+    /// Title to identify a film.
+    /// Parameter originally sent from the user.
+    pub query: String,
+    /// Full title of the film, as returned by Swapi.
+    pub film: String,
+    pub yoda_height: Height,
+    /// Characters of the film shorter than Yoda,
+    /// and errors for the characters that couldn't be compared with him.
+    pub characters: Vec<FilmCharacterEntry>,
+}
+
+/// A character of a film in a [`ShorterThanYodaResponse`].
+#[derive(Debug, serde::Serialize)]
+#[serde(untagged)]
+// derive only for tests
+#[cfg_attr(feature = "test_fixture", derive(serde::Deserialize, PartialEq, Eq))]
+pub enum FilmCharacterEntry {
+    Shorter(Character),
+    Error(ErrorBody),
+}
+
+pub async fn shorter_than_yoda(
+    Path(title): Path<String>,
+    Extension(yoda_taller): Extension<Arc<YodaTaller>>,
+) -> Result<Json<ShorterThanYodaResponse>, YodaTallerResponseError> {
+    match yoda_taller.shorter_than_yoda_in_film(&title).await {
+        Ok(FilmComparison { film, characters }) => {
+            let characters = characters
+                .into_iter()
+                .map(|character| match character {
+                    Ok(character) => FilmCharacterEntry::Shorter(character),
+                    Err(ComparisonError { query, error }) => {
+                        FilmCharacterEntry::Error(YodaTallerResponseError { query, error }.into())
+                    }
+                })
+                .collect();
+            let json_response = ShorterThanYodaResponse {
+                query: title,
+                film,
+                yoda_height: Character::yoda().height,
+                characters,
+            }
+            .into();
+            Ok(json_response)
+        }
+        Err(e) => {
+            log_error(&e);
+            Err(YodaTallerResponseError {
+                query: title,
+                error: e,
             })
         }
     }
 }
 
+/// Query parameters of the rank route.
+#[derive(Debug, Default, serde::Deserialize)]
+pub struct RankParams {
+    /// Comma separated names of the people to rank.
+    #[serde(default)]
+    pub names: String,
+    #[serde(flatten)]
+    pub lookup: LookupOptions,
+}
+
+#[derive(Debug, serde::Serialize)]
+// derive only for tests
+#[cfg_attr(feature = "test_fixture", derive(serde::Deserialize, PartialEq, Eq))]
+pub struct RankResponse {
+    /// Yoda and the people with a known height, from the tallest to the shortest.
+    pub ranked: Vec<RankedCharacter>,
+    /// Position of Yoda in `ranked`.
+    pub yoda_position: usize,
+    /// People whose height is unknown.
+    pub unranked: Vec<ErrorBody>,
+    /// Names that couldn't be resolved into a person.
+    pub errors: Vec<ErrorBody>,
+    /// When the people were last copied from Swapi, if they are served from a local copy.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub synced_at: Option<DateTime<Utc>>,
+}
+
+pub async fn rank(
+    params: Result<Query<RankParams>, QueryRejection>,
+    Extension(yoda_taller): Extension<Arc<YodaTaller>>,
+    Extension(BatchConcurrency(concurrency)): Extension<BatchConcurrency>,
+    Extension(MaxRankNames(max_names)): Extension<MaxRankNames>,
+) -> Result<Json<RankResponse>, Response> {
+    // The names are unknown if the query parameters can't be read.
+    let params = query_params("", params).map_err(IntoResponse::into_response)?;
+    let names: Vec<String> = params
+        .names
+        .split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(str::to_string)
+        .collect();
+    if names.len() > max_names {
+        let error = TooManyNamesError {
+            query: names.join(","),
+            max: max_names,
+        };
+        warn!("{} names to rank, at most {max_names} allowed", names.len());
+        return Err(error.into_response());
+    }
+    let HeightRanking { ranked, failures } = yoda_taller
+        .rank_by_height(&names, &params.lookup, concurrency)
+        .await;
+    let yoda_position = ranked
+        .iter()
+        .find(|character| character.yoda)
+        .map(|character| character.position)
+        .unwrap_or_default();
+    let (mut unranked, mut errors) = (vec![], vec![]);
+    for ComparisonError { query, error } in failures {
+        log_error(&error);
+        let height_unknown = matches!(error, YodaTallerError::HeightNotFound);
+        let body = YodaTallerResponseError { query, error }.into();
+        if height_unknown {
+            unranked.push(body);
+        } else {
+            errors.push(body);
+        }
+    }
+    Ok(Json(RankResponse {
+        ranked,
+        yoda_position,
+        unranked,
+        errors,
+        synced_at: yoda_taller.synced_at().await,
+    }))
+}
+
+#[derive(Debug, serde::Serialize)]
+// derive only for tests
+#[cfg_attr(feature = "test_fixture", derive(serde::Deserialize, PartialEq, Eq))]
+pub struct MatrixResponse {
+    /// Names to identify the people.
+    /// Parameters originally sent from the user.
+    pub queries: Vec<String>,
+    /// `relations[i][j]` tells how the person of `queries[i]` relates to the person of `queries[j]`.
+    /// `null` if either of them couldn't be resolved.
+    pub relations: Vec<Vec<Option<Relation>>>,
+    /// Names that couldn't be resolved, each reported once.
+    pub errors: Vec<ErrorBody>,
+    /// When the people were last copied from Swapi, if they are served from a local copy.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub synced_at: Option<DateTime<Utc>>,
+}
+
+pub async fn matrix(
+    params: Result<Query<LookupOptions>, QueryRejection>,
+    Extension(yoda_taller): Extension<Arc<YodaTaller>>,
+    Extension(BatchConcurrency(concurrency)): Extension<BatchConcurrency>,
+    Extension(MaxMatrixNames(max_names)): Extension<MaxMatrixNames>,
+    names: Result<Json<Vec<String>>, JsonRejection>,
+) -> Result<Json<MatrixResponse>, Response> {
+    let names = json_body(names).map_err(IntoResponse::into_response)?;
+    let options = query_params(&names.join(","), params).map_err(IntoResponse::into_response)?;
+    if names.len() > max_names {
+        let error = TooManyNamesError {
+            query: names.join(","),
+            max: max_names,
+        };
+        warn!(
+            "{} names to compare, at most {max_names} allowed",
+            names.len()
+        );
+        return Err(error.into_response());
+    }
+    let HeightMatrix {
+        relations,
+        failures,
+    } = yoda_taller
+        .height_matrix(&names, &options, concurrency)
+        .await;
+    let errors = failures
+        .into_iter()
+        .map(|ComparisonError { query, error }| {
+            log_error(&error);
+            YodaTallerResponseError { query, error }.into()
+        })
+        .collect();
+    Ok(Json(MatrixResponse {
+        queries: names,
+        relations,
+        errors,
+        synced_at: yoda_taller.synced_at().await,
+    }))
+}
+
 fn log_error(e: &YodaTallerError) {
     match e {
-        YodaTallerError::HeightNotFound | YodaTallerError::PersonNotFound => {
+        YodaTallerError::HeightNotFound
+        | YodaTallerError::MassNotFound
+        | YodaTallerError::BirthYearNotFound
+        | YodaTallerError::PersonNotFound
+        | YodaTallerError::FilmNotFound
+        | YodaTallerError::Ambiguous { .. }
+        | YodaTallerError::AmbiguousFilm { .. }
+        | YodaTallerError::CircuitOpen { .. } => {
             warn!("{e}")
         }
-        YodaTallerError::UnexpectedError(_) => error!("{e}"),
+        YodaTallerError::UnexpectedError(_) | YodaTallerError::DatabaseError(_) => error!("{e}"),
     }
 }
 
-impl IntoResponse for YodaTallerResponseError {
-    fn into_response(self) -> axum::response::Response {
-        let (status_code, error_message) = match self.error {
-            YodaTallerError::HeightNotFound | YodaTallerError::PersonNotFound => {
-                (StatusCode::NOT_FOUND, format!("{}", self.error))
+impl YodaTallerResponseError {
+    fn status_code(&self) -> StatusCode {
+        match self.error {
+            YodaTallerError::HeightNotFound
+            | YodaTallerError::MassNotFound
+            | YodaTallerError::BirthYearNotFound
+            | YodaTallerError::PersonNotFound
+            | YodaTallerError::FilmNotFound => StatusCode::NOT_FOUND,
+            YodaTallerError::Ambiguous { .. } | YodaTallerError::AmbiguousFilm { .. } => {
+                StatusCode::MULTIPLE_CHOICES
+            }
+            YodaTallerError::CircuitOpen { .. } => StatusCode::SERVICE_UNAVAILABLE,
+            YodaTallerError::UnexpectedError(_) | YodaTallerError::DatabaseError(_) => {
+                StatusCode::INTERNAL_SERVER_ERROR
+            }
+        }
+    }
+}
+
+impl From<YodaTallerResponseError> for ErrorBody {
+    fn from(response_error: YodaTallerResponseError) -> Self {
+        let error = match response_error.error {
+            YodaTallerError::UnexpectedError(_) | YodaTallerError::DatabaseError(_) => {
+                "Unexpected error".to_string()
             }
-            YodaTallerError::UnexpectedError(_) => (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                "Unexpected error".to_string(),
-            ),
+            _ => format!("{}", response_error.error),
+        };
+        let candidates = match response_error.error {
+            YodaTallerError::Ambiguous { candidates }
+            | YodaTallerError::AmbiguousFilm { candidates } => candidates,
+            _ => vec![],
         };
+        Self {
+            query: response_error.query,
+            error,
+            candidates,
+        }
+    }
+}
 
+impl IntoResponse for YodaTallerResponseError {
+    fn into_response(self) -> axum::response::Response {
+        let status_code = self.status_code();
+        let mut headers = HeaderMap::new();
+        if let YodaTallerError::CircuitOpen { retry_after } = self.error {
+            // Round up, so that the circuit is closed when the client retries.
+            let seconds = retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0);
+            headers.insert(header::RETRY_AFTER, HeaderValue::from(seconds.max(1)));
+        }
+        (status_code, headers, Json(ErrorBody::from(self))).into_response()
+    }
+}
+
+impl IntoResponse for InvalidParamsError {
+    fn into_response(self) -> axum::response::Response {
         (
-            status_code,
+            StatusCode::BAD_REQUEST,
             Json(ErrorBody {
                 query: self.query,
-                error: error_message,
+                error: self.rejection.to_string(),
+                candidates: vec![],
             }),
         )
             .into_response()
     }
 }
 
-#[derive(Serialize)]
+impl IntoResponse for TooManyNamesError {
+    fn into_response(self) -> axum::response::Response {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorBody {
+                query: self.query,
+                error: format!("Too many names, at most {} are allowed", self.max),
+                candidates: vec![],
+            }),
+        )
+            .into_response()
+    }
+}
+
+impl IntoResponse for InvalidBodyError {
+    fn into_response(self) -> axum::response::Response {
+        let error = self.rejection.to_string();
+        // Keep the status of the rejection, such as `415 Unsupported Media Type`.
+        let status_code = self.rejection.into_response().status();
+        (
+            status_code,
+            Json(ErrorBody {
+                // The names are unknown if the body can't be read.
+                query: String::new(),
+                error,
+                candidates: vec![],
+            }),
+        )
+            .into_response()
+    }
+}
+
+#[derive(Debug, Serialize)]
 // derive deserialize only on tests
-#[cfg_attr(
-    feature = "test_fixture",
-    derive(serde::Deserialize, Debug, PartialEq, Eq)
-)]
+#[cfg_attr(feature = "test_fixture", derive(serde::Deserialize, PartialEq, Eq))]
 pub struct ErrorBody {
     pub query: String,
     /// Error message
     pub error: String,
+    /// People or films matching the query, if it is ambiguous.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub candidates: Vec<Candidate>,
 }
@@ -1,27 +1,208 @@
 use {
-    crate::taller::YodaTaller,
+    crate::{
+        source::{CircuitBreaker, Dataset, PeopleCache, PeopleSource, SqliteMirror},
+        swapi::{Cassette, CassetteMode, SwapiClient},
+        taller::YodaTaller,
+    },
     anyhow::Context,
-    std::{path::Path, time::Duration},
+    std::{
+        path::{Path, PathBuf},
+        sync::Arc,
+        time::Duration,
+    },
 };
 
-#[cfg(feature = "test_fixture")]
-use crate::swapi::SwapiClient;
-
 #[derive(serde::Deserialize, Clone)]
 pub struct Settings {
     pub application: ApplicationSettings,
     pub swapi: SwapiSettings,
+    #[serde(default)]
+    pub stats: StatsSettings,
 }
 
 #[derive(serde::Deserialize, Clone)]
 pub struct ApplicationSettings {
     pub port: u16,
+    /// Maximum number of names of a batch request looked up at the same time.
+    #[serde(default = "default_batch_concurrency")]
+    pub batch_concurrency: usize,
+    /// Maximum number of names of a rank request.
+    #[serde(default = "default_max_rank_names")]
+    pub max_rank_names: usize,
+    /// Maximum number of names of a matrix request, which compares each name with each other.
+    #[serde(default = "default_max_matrix_names")]
+    pub max_matrix_names: usize,
+}
+
+fn default_batch_concurrency() -> usize {
+    8
+}
+
+fn default_max_rank_names() -> usize {
+    100
+}
+
+fn default_max_matrix_names() -> usize {
+    20
 }
 
 #[derive(serde::Deserialize, Clone)]
 pub struct SwapiSettings {
     pub base_url: String,
     pub timeout_milliseconds: u64,
+    /// Maximum number of result pages to retrieve for a single search.
+    #[serde(default = "default_max_pages")]
+    pub max_pages: usize,
+    /// Where people are looked up by name.
+    #[serde(default)]
+    pub mode: SwapiMode,
+    /// Swapi dump of people, used in [`SwapiMode::File`] mode.
+    #[serde(default)]
+    pub dataset_path: Option<PathBuf>,
+    /// SQLite copy of Swapi, filled by `yoda-taller sync` and used in [`SwapiMode::Sqlite`] mode.
+    #[serde(default)]
+    pub database_path: Option<PathBuf>,
+    /// Whether to record the responses of Swapi to `cassette_path`, or to replay them from it.
+    #[serde(default)]
+    pub cassette: CassetteMode,
+    #[serde(default)]
+    pub cassette_path: Option<PathBuf>,
+    /// Cache in front of the people search.
+    #[serde(default)]
+    pub cache: CacheSettings,
+    /// Circuit breaker in front of the people search, in http mode.
+    #[serde(default)]
+    pub circuit_breaker: CircuitBreakerSettings,
+}
+
+#[derive(serde::Deserialize, Clone)]
+pub struct CircuitBreakerSettings {
+    /// Number of failed searches in a row after which Swapi isn't called anymore.
+    #[serde(default = "default_failure_threshold")]
+    pub failure_threshold: u32,
+    /// How long Swapi isn't called once the circuit is open,
+    /// before trying it again with a single search.
+    #[serde(default = "default_open_milliseconds")]
+    pub open_milliseconds: u64,
+}
+
+fn default_failure_threshold() -> u32 {
+    5
+}
+
+fn default_open_milliseconds() -> u64 {
+    30 * 1000
+}
+
+impl Default for CircuitBreakerSettings {
+    fn default() -> Self {
+        Self {
+            failure_threshold: default_failure_threshold(),
+            open_milliseconds: default_open_milliseconds(),
+        }
+    }
+}
+
+impl CircuitBreakerSettings {
+    pub fn open_duration(&self) -> Duration {
+        Duration::from_millis(self.open_milliseconds)
+    }
+}
+
+#[derive(serde::Deserialize, Clone)]
+pub struct CacheSettings {
+    #[serde(default)]
+    pub enabled: bool,
+    /// How long the people found for a name are kept.
+    #[serde(default = "default_cache_ttl_milliseconds")]
+    pub ttl_milliseconds: u64,
+    /// Maximum number of names whose people are kept at the same time.
+    #[serde(default = "default_cache_max_entries")]
+    pub max_entries: usize,
+    /// Whether to also keep searches that didn't find anyone,
+    /// so that unknown names don't reach Swapi again.
+    #[serde(default)]
+    pub cache_not_found: bool,
+    /// Whether to keep expired searches, to serve them when Swapi fails or times out
+    /// while they are refreshed in the background.
+    #[serde(default)]
+    pub serve_stale: bool,
+}
+
+fn default_cache_ttl_milliseconds() -> u64 {
+    60 * 1000
+}
+
+fn default_cache_max_entries() -> usize {
+    1000
+}
+
+impl Default for CacheSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            ttl_milliseconds: default_cache_ttl_milliseconds(),
+            max_entries: default_cache_max_entries(),
+            cache_not_found: false,
+            serve_stale: false,
+        }
+    }
+}
+
+impl CacheSettings {
+    pub fn ttl(&self) -> Duration {
+        Duration::from_millis(self.ttl_milliseconds)
+    }
+}
+
+#[derive(serde::Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SwapiMode {
+    /// Search people through the Swapi API.
+    #[default]
+    Http,
+    /// Search people in the dump at `dataset_path`, without network.
+    File,
+    /// Search people in the SQLite copy of Swapi at `database_path`, without network.
+    Sqlite,
+}
+
+fn default_max_pages() -> usize {
+    10
+}
+
+#[derive(serde::Deserialize, Clone)]
+pub struct StatsSettings {
+    /// Width of the buckets of the height histogram.
+    #[serde(default = "default_bucket_width_cm")]
+    pub bucket_width_cm: u32,
+    /// How often the height statistics are computed again.
+    /// `0` computes them once at startup, without ever refreshing them.
+    #[serde(default = "default_refresh_interval_milliseconds")]
+    pub refresh_interval_milliseconds: u64,
+}
+
+fn default_bucket_width_cm() -> u32 {
+    25
+}
+
+fn default_refresh_interval_milliseconds() -> u64 {
+    60 * 60 * 1000
+}
+
+impl Default for StatsSettings {
+    fn default() -> Self {
+        Self {
+            bucket_width_cm: default_bucket_width_cm(),
+            refresh_interval_milliseconds: default_refresh_interval_milliseconds(),
+        }
+    }
+}
+
+impl StatsSettings {
+    pub fn refresh_interval(&self) -> Duration {
+        Duration::from_millis(self.refresh_interval_milliseconds)
+    }
 }
 
 impl SwapiSettings {
@@ -30,18 +211,63 @@ impl SwapiSettings {
     }
 
     pub fn yoda_taller(&self) -> anyhow::Result<YodaTaller> {
-        YodaTaller::new(self.base_url.clone(), self.timeout())
+        let swapi_client = self.swapi_client()?;
+        let people: Arc<dyn PeopleSource> = match self.mode {
+            SwapiMode::Http => Arc::new(CircuitBreaker::new(
+                Arc::new(swapi_client.clone()),
+                self.circuit_breaker.failure_threshold,
+                self.circuit_breaker.open_duration(),
+            )),
+            SwapiMode::File => {
+                let dataset_path = self
+                    .dataset_path
+                    .as_ref()
+                    .context("dataset_path is required in file mode")?;
+                Arc::new(Dataset::load(dataset_path)?)
+            }
+            SwapiMode::Sqlite => Arc::new(self.sqlite_mirror()?),
+        };
+        let people: Arc<dyn PeopleSource> = if self.cache.enabled {
+            Arc::new(PeopleCache::new(
+                people,
+                self.cache.ttl(),
+                self.cache.max_entries,
+                self.cache.cache_not_found,
+                self.cache.serve_stale,
+            ))
+        } else {
+            people
+        };
+        Ok(YodaTaller::new(people, swapi_client))
+    }
+
+    pub fn sqlite_mirror(&self) -> anyhow::Result<SqliteMirror> {
+        let database_path = self
+            .database_path
+            .as_ref()
+            .context("database_path is required to use the people database")?;
+        SqliteMirror::open(database_path)
     }
 
-    #[cfg(feature = "test_fixture")]
     pub fn swapi_client(&self) -> anyhow::Result<SwapiClient> {
-        SwapiClient::new(self.base_url.clone(), self.timeout())
+        let swapi_client = SwapiClient::new(self.base_url.clone(), self.timeout(), self.max_pages)?;
+        let cassette_path = || {
+            self.cassette_path
+                .as_deref()
+                .context("cassette_path is required to record or replay Swapi responses")
+        };
+        let cassette = match self.cassette {
+            CassetteMode::Passthrough => return Ok(swapi_client),
+            CassetteMode::Record => Cassette::for_recording(cassette_path()?)?,
+            CassetteMode::Replay => Cassette::load(cassette_path()?)?,
+        };
+        Ok(swapi_client.with_cassette(cassette))
     }
 }
 
 impl Settings {
     pub fn read(config_file: &Path) -> anyhow::Result<Self> {
-        let f = std::fs::File::open(&config_file)
+        let f = std::fs::File::open(config_file)
             .with_context(|| format!("cannot open config file {:?}", config_file))?;
         serde_yaml::from_reader(f).context("invalid config file format")
     }
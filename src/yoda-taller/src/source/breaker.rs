@@ -0,0 +1,127 @@
+use {
+    super::PeopleSource,
+    crate::{swapi::Person, taller::YodaTallerError},
+    async_trait::async_trait,
+    std::{
+        sync::{Arc, Mutex},
+        time::{Duration, Instant},
+    },
+    tracing::warn,
+};
+
+/// Stop searching another source for a while after it failed too many times in a row,
+/// so that requests fail right away instead of waiting for it to time out.
+pub struct CircuitBreaker {
+    source: Arc<dyn PeopleSource>,
+    /// Number of failures in a row opening the circuit.
+    failure_threshold: u32,
+    /// How long the circuit stays open before trying the other source again.
+    open_duration: Duration,
+    state: Mutex<State>,
+}
+
+enum State {
+    /// The other source is searched.
+    Closed { failures: u32 },
+    /// The other source isn't searched until the given instant.
+    Open { until: Instant },
+    /// A single search of the other source is in progress to decide whether it recovered.
+    /// Other searches are rejected until it completes, or until the given instant
+    /// if it is abandoned.
+    HalfOpen { until: Instant },
+}
+
+/// State of a circuit breaker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+// derive only for tests
+#[cfg_attr(feature = "test_fixture", derive(serde::Deserialize))]
+#[serde(rename_all = "snake_case")]
+pub enum CircuitState {
+    Closed,
+    Open,
+    HalfOpen,
+}
+
+impl CircuitBreaker {
+    pub fn new(
+        source: Arc<dyn PeopleSource>,
+        failure_threshold: u32,
+        open_duration: Duration,
+    ) -> Self {
+        Self {
+            source,
+            failure_threshold,
+            open_duration,
+            state: Mutex::new(State::Closed { failures: 0 }),
+        }
+    }
+
+    /// Whether the other source can be searched.
+    fn allow(&self) -> Result<(), YodaTallerError> {
+        let mut state = self.state.lock().unwrap();
+        let now = Instant::now();
+        match *state {
+            State::Closed { .. } => Ok(()),
+            State::Open { until } | State::HalfOpen { until } if now < until => {
+                Err(YodaTallerError::CircuitOpen {
+                    retry_after: until - now,
+                })
+            }
+            State::Open { .. } | State::HalfOpen { .. } => {
+                *state = State::HalfOpen {
+                    until: now + self.open_duration,
+                };
+                Ok(())
+            }
+        }
+    }
+
+    /// Update the state with the outcome of a search of the other source.
+    fn record<T>(&self, result: &Result<T, YodaTallerError>) {
+        let failed = matches!(result, Err(YodaTallerError::UnexpectedError(_)));
+        let mut state = self.state.lock().unwrap();
+        if !failed {
+            *state = State::Closed { failures: 0 };
+            return;
+        }
+        let failures = match *state {
+            State::Closed { failures } => failures + 1,
+            // The trial search failed.
+            State::HalfOpen { .. } => self.failure_threshold,
+            // Searches started before the circuit opened.
+            State::Open { .. } => return,
+        };
+        *state = if failures >= self.failure_threshold {
+            warn!("opening the circuit after {failures} failures");
+            State::Open {
+                until: Instant::now() + self.open_duration,
+            }
+        } else {
+            State::Closed { failures }
+        };
+    }
+}
+
+#[async_trait]
+impl PeopleSource for CircuitBreaker {
+    async fn people_by_name(&self, name: &str) -> Result<Vec<Person>, YodaTallerError> {
+        self.allow()?;
+        let people = self.source.people_by_name(name).await;
+        self.record(&people);
+        people
+    }
+
+    /// Listing everyone is left out of the circuit, as it only runs in the background.
+    async fn all_people(&self) -> Result<Vec<Person>, YodaTallerError> {
+        self.source.all_people().await
+    }
+
+    fn circuit_state(&self) -> Option<CircuitState> {
+        let state = match *self.state.lock().unwrap() {
+            State::Closed { .. } => CircuitState::Closed,
+            State::Open { .. } => CircuitState::Open,
+            State::HalfOpen { .. } => CircuitState::HalfOpen,
+        };
+        Some(state)
+    }
+}
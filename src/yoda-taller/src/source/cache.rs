@@ -0,0 +1,235 @@
+use {
+    super::{search_key, CircuitState, Found, PeopleSource},
+    crate::{swapi::Person, taller::YodaTallerError},
+    async_trait::async_trait,
+    chrono::{DateTime, Utc},
+    std::{
+        collections::HashMap,
+        sync::{
+            atomic::{AtomicU64, Ordering},
+            Arc, Mutex,
+        },
+        time::{Duration, Instant},
+    },
+    tracing::warn,
+};
+
+/// Remember the people found by another source for a while,
+/// so that searching the same name again doesn't reach the other source.
+pub struct PeopleCache {
+    source: Arc<dyn PeopleSource>,
+    store: Arc<Store>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+/// Search results kept by the cache.
+struct Store {
+    /// How long search results are fresh.
+    ttl: Duration,
+    /// Maximum number of search results kept at the same time.
+    max_entries: usize,
+    /// Whether to keep search results without any person.
+    cache_not_found: bool,
+    /// Whether to keep expired search results, to serve them when the other source fails.
+    serve_stale: bool,
+    entries: Mutex<HashMap<String, Entry>>,
+}
+
+struct Entry {
+    people: Vec<Person>,
+    stored_at: Instant,
+    /// The last search of the other source failed, so that this entry is served stale
+    /// without waiting for the other source until a background refresh succeeds.
+    failing: bool,
+    /// A background refresh of this entry is in progress.
+    refreshing: bool,
+}
+
+/// How well the cache performs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize)]
+// derive only for tests
+#[cfg_attr(feature = "test_fixture", derive(serde::Deserialize))]
+pub struct CacheStats {
+    /// Searches answered from the cache.
+    pub hits: u64,
+    /// Searches forwarded to the cached source.
+    pub misses: u64,
+    /// Search results currently kept, including expired ones not evicted yet.
+    pub entries: usize,
+}
+
+impl PeopleCache {
+    pub fn new(
+        source: Arc<dyn PeopleSource>,
+        ttl: Duration,
+        max_entries: usize,
+        cache_not_found: bool,
+        serve_stale: bool,
+    ) -> Self {
+        let store = Store {
+            ttl,
+            max_entries,
+            cache_not_found,
+            serve_stale,
+            entries: Mutex::default(),
+        };
+        Self {
+            source,
+            store: Arc::new(store),
+            hits: AtomicU64::default(),
+            misses: AtomicU64::default(),
+        }
+    }
+
+    /// Search the other source again without making the current search wait for it.
+    /// The entry is updated if the other source answers.
+    fn refresh_in_background(&self, key: String) {
+        if !self.store.start_refresh(&key) {
+            return;
+        }
+        let source = Arc::clone(&self.source);
+        let store = Arc::clone(&self.store);
+        tokio::spawn(async move {
+            match source.people_by_name(&key).await {
+                Ok(people) => store.insert(key, &people),
+                Err(e) => {
+                    warn!("cannot refresh stale people: {e}");
+                    store.finish_refresh(&key);
+                }
+            }
+        });
+    }
+}
+
+impl Store {
+    /// The people found for the given search, and how long ago they were found.
+    fn get(&self, key: &str) -> Option<(Vec<Person>, Duration, bool)> {
+        let entries = self.entries.lock().unwrap();
+        let entry = entries.get(key)?;
+        Some((
+            entry.people.clone(),
+            entry.stored_at.elapsed(),
+            entry.failing,
+        ))
+    }
+
+    fn insert(&self, key: String, people: &[Person]) {
+        let mut entries = self.entries.lock().unwrap();
+        if self.max_entries == 0 || (people.is_empty() && !self.cache_not_found) {
+            entries.remove(&key);
+            return;
+        }
+        if !self.serve_stale {
+            let ttl = self.ttl;
+            entries.retain(|_, entry| entry.stored_at.elapsed() < ttl);
+        }
+        if entries.len() >= self.max_entries && !entries.contains_key(&key) {
+            let oldest = entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.stored_at)
+                .map(|(key, _)| key.clone());
+            if let Some(oldest) = oldest {
+                entries.remove(&oldest);
+            }
+        }
+        let entry = Entry {
+            people: people.to_vec(),
+            stored_at: Instant::now(),
+            failing: false,
+            refreshing: false,
+        };
+        entries.insert(key, entry);
+    }
+
+    /// Remember that the other source failed to search again the given entry,
+    /// returning the stale people to serve instead.
+    fn fail(&self, key: &str) -> Option<Found> {
+        let mut entries = self.entries.lock().unwrap();
+        let entry = entries.get_mut(key)?;
+        entry.failing = true;
+        Some(Found::stale(
+            entry.people.clone(),
+            entry.stored_at.elapsed(),
+        ))
+    }
+
+    /// Whether a background refresh of the entry must be started.
+    fn start_refresh(&self, key: &str) -> bool {
+        let mut entries = self.entries.lock().unwrap();
+        match entries.get_mut(key) {
+            Some(entry) if !entry.refreshing => {
+                entry.refreshing = true;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn finish_refresh(&self, key: &str) {
+        if let Some(entry) = self.entries.lock().unwrap().get_mut(key) {
+            entry.refreshing = false;
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.entries.lock().unwrap().len()
+    }
+}
+
+#[async_trait]
+impl PeopleSource for PeopleCache {
+    async fn people_by_name(&self, name: &str) -> Result<Vec<Person>, YodaTallerError> {
+        Ok(self.search(name).await?.people)
+    }
+
+    async fn search(&self, name: &str) -> Result<Found, YodaTallerError> {
+        // The other source is searched with the key, so that the cached people
+        // are the ones found for every name sharing this key.
+        let key = search_key(name);
+        match self.store.get(&key) {
+            Some((people, age, _)) if age < self.store.ttl => {
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                return Ok(Found::fresh(people));
+            }
+            Some((people, age, true)) if self.store.serve_stale => {
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                self.refresh_in_background(key);
+                return Ok(Found::stale(people, age));
+            }
+            _ => {}
+        }
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        match self.source.search(&key).await {
+            Ok(found) => {
+                self.store.insert(key, &found.people);
+                Ok(found)
+            }
+            Err(e) if self.store.serve_stale => {
+                warn!("serving stale people: {e}");
+                self.store.fail(&key).ok_or(e)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn all_people(&self) -> Result<Vec<Person>, YodaTallerError> {
+        self.source.all_people().await
+    }
+
+    async fn synced_at(&self) -> Option<DateTime<Utc>> {
+        self.source.synced_at().await
+    }
+
+    fn circuit_state(&self) -> Option<CircuitState> {
+        self.source.circuit_state()
+    }
+
+    fn cache_stats(&self) -> Option<CacheStats> {
+        Some(CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            entries: self.store.len(),
+        })
+    }
+}
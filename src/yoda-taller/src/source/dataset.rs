@@ -0,0 +1,62 @@
+use {
+    super::PeopleSource,
+    crate::{
+        swapi::{Person, Query},
+        taller::YodaTallerError,
+    },
+    anyhow::Context,
+    async_trait::async_trait,
+    std::{fs::File, io::BufReader, path::Path},
+};
+
+/// People loaded in memory from a dump of Swapi, to work without network.
+#[derive(Debug, Clone)]
+pub struct Dataset {
+    people: Vec<Person>,
+}
+
+/// Content of a dump file: either a list of people,
+/// or a page of results as returned by the Swapi people resource.
+#[derive(serde::Deserialize)]
+#[serde(untagged)]
+enum Dump {
+    People(Vec<Person>),
+    Page(Query<Person>),
+}
+
+impl Dataset {
+    pub fn new(people: Vec<Person>) -> Self {
+        Self { people }
+    }
+
+    /// Load the people of the dump file at the given path.
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let f = File::open(path).with_context(|| format!("cannot open dataset {:?}", path))?;
+        let dump = serde_json::from_reader(BufReader::new(f))
+            .with_context(|| format!("invalid dataset format in {:?}", path))?;
+        let people = match dump {
+            Dump::People(people) => people,
+            Dump::Page(page) => page.results,
+        };
+        tracing::info!("loaded {} people from {:?}", people.len(), path);
+        Ok(Self::new(people))
+    }
+}
+
+#[async_trait]
+impl PeopleSource for Dataset {
+    async fn people_by_name(&self, name: &str) -> Result<Vec<Person>, YodaTallerError> {
+        let name = name.to_lowercase();
+        let people = self
+            .people
+            .iter()
+            .filter(|person| person.name.to_lowercase().contains(&name))
+            .cloned()
+            .collect();
+        Ok(people)
+    }
+
+    async fn all_people(&self) -> Result<Vec<Person>, YodaTallerError> {
+        Ok(self.people.clone())
+    }
+}
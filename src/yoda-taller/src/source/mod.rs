@@ -0,0 +1,98 @@
+//! Where people come from.
+
+mod breaker;
+mod cache;
+mod dataset;
+mod single_flight;
+mod sqlite;
+
+use {
+    crate::{
+        swapi::{Person, SwapiClient},
+        taller::YodaTallerError,
+    },
+    async_trait::async_trait,
+    chrono::{DateTime, Utc},
+    std::time::Duration,
+};
+pub use {
+    breaker::{CircuitBreaker, CircuitState},
+    cache::{CacheStats, PeopleCache},
+    dataset::Dataset,
+    single_flight::SingleFlight,
+    sqlite::{SqliteMirror, SyncReport},
+};
+
+/// A source of Star Wars people that can be searched by name.
+#[async_trait]
+pub trait PeopleSource: Send + Sync {
+    /// All the people whose name contains the given one, ignoring case, as Swapi does.
+    async fn people_by_name(&self, name: &str) -> Result<Vec<Person>, YodaTallerError>;
+
+    /// Same as [`PeopleSource::people_by_name`], telling whether the people are stale.
+    async fn search(&self, name: &str) -> Result<Found, YodaTallerError> {
+        let people = self.people_by_name(name).await?;
+        Ok(Found::fresh(people))
+    }
+
+    /// All the people of this source.
+    async fn all_people(&self) -> Result<Vec<Person>, YodaTallerError>;
+
+    /// When the people of this source were last copied from Swapi,
+    /// if they aren't retrieved from Swapi when searched.
+    async fn synced_at(&self) -> Option<DateTime<Utc>> {
+        None
+    }
+
+    /// How well the cache in front of the people performs, if there is one.
+    fn cache_stats(&self) -> Option<CacheStats> {
+        None
+    }
+
+    /// State of the circuit breaker in front of the people, if there is one.
+    fn circuit_state(&self) -> Option<CircuitState> {
+        None
+    }
+}
+
+/// People found by a search.
+#[derive(Debug, Clone)]
+pub struct Found {
+    pub people: Vec<Person>,
+    /// How long ago the people were retrieved, if they are served stale
+    /// because their source failed to answer.
+    pub stale_for: Option<Duration>,
+}
+
+impl Found {
+    pub fn fresh(people: Vec<Person>) -> Self {
+        Self {
+            people,
+            stale_for: None,
+        }
+    }
+
+    pub fn stale(people: Vec<Person>, age: Duration) -> Self {
+        Self {
+            people,
+            stale_for: Some(age),
+        }
+    }
+}
+
+/// Swapi itself, following the result pages of a search.
+#[async_trait]
+impl PeopleSource for SwapiClient {
+    async fn people_by_name(&self, name: &str) -> Result<Vec<Person>, YodaTallerError> {
+        Ok(self.all_people_by_name(name).await?)
+    }
+
+    async fn all_people(&self) -> Result<Vec<Person>, YodaTallerError> {
+        Ok(SwapiClient::all_people(self).await?)
+    }
+}
+
+/// Searches differing only by case or surrounding whitespace find the same people.
+fn search_key(name: &str) -> String {
+    name.trim().to_lowercase()
+}
@@ -0,0 +1,80 @@
+use {
+    super::{search_key, CacheStats, CircuitState, Found, PeopleSource},
+    crate::{swapi::Person, taller::YodaTallerError},
+    async_trait::async_trait,
+    chrono::{DateTime, Utc},
+    futures::future::{BoxFuture, FutureExt, Shared},
+    std::{
+        collections::HashMap,
+        sync::{Arc, Mutex},
+    },
+};
+
+type SharedSearch = Shared<BoxFuture<'static, Result<Found, YodaTallerError>>>;
+
+/// Share one search of another source between the concurrent searches of the same name,
+/// so that a popular name reaches the other source once even when asked by many clients.
+pub struct SingleFlight {
+    source: Arc<dyn PeopleSource>,
+    /// Searches in progress, by normalized name.
+    in_flight: Arc<Mutex<HashMap<String, SharedSearch>>>,
+}
+
+impl SingleFlight {
+    pub fn new(source: Arc<dyn PeopleSource>) -> Self {
+        Self {
+            source,
+            in_flight: Arc::default(),
+        }
+    }
+
+    /// The search in progress for the given name, started if there is none.
+    fn in_flight_search(&self, name: &str) -> SharedSearch {
+        let key = search_key(name);
+        let mut in_flight = self.in_flight.lock().unwrap();
+        if let Some(search) = in_flight.get(&key) {
+            return search.clone();
+        }
+        let source = Arc::clone(&self.source);
+        let searches = Arc::clone(&self.in_flight);
+        let finished_key = key.clone();
+        let search = async move {
+            // Searching the key, the result doesn't depend on which name came first.
+            let found = source.search(&finished_key).await;
+            // Waiters already hold the search, later searches must start a new one.
+            searches.lock().unwrap().remove(&finished_key);
+            found
+        }
+        .boxed()
+        .shared();
+        in_flight.insert(key, search.clone());
+        search
+    }
+}
+
+#[async_trait]
+impl PeopleSource for SingleFlight {
+    async fn people_by_name(&self, name: &str) -> Result<Vec<Person>, YodaTallerError> {
+        Ok(self.search(name).await?.people)
+    }
+
+    async fn search(&self, name: &str) -> Result<Found, YodaTallerError> {
+        self.in_flight_search(name).await
+    }
+
+    async fn all_people(&self) -> Result<Vec<Person>, YodaTallerError> {
+        self.source.all_people().await
+    }
+
+    async fn synced_at(&self) -> Option<DateTime<Utc>> {
+        self.source.synced_at().await
+    }
+
+    fn circuit_state(&self) -> Option<CircuitState> {
+        self.source.circuit_state()
+    }
+
+    fn cache_stats(&self) -> Option<CacheStats> {
+        self.source.cache_stats()
+    }
+}
@@ -0,0 +1,180 @@
+use {
+    super::PeopleSource,
+    crate::{
+        swapi::{Person, SwapiClient},
+        taller::YodaTallerError,
+    },
+    anyhow::Context,
+    async_trait::async_trait,
+    chrono::{DateTime, Utc},
+    rusqlite::{params, Connection, OptionalExtension},
+    std::{
+        path::Path,
+        sync::{Arc, Mutex},
+    },
+};
+
+/// Local copy of the Swapi people, stored in SQLite by `yoda-taller sync`.
+#[derive(Clone)]
+pub struct SqliteMirror {
+    connection: Arc<Mutex<Connection>>,
+}
+
+/// What a sync changed in the mirror.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SyncReport {
+    /// People that weren't in the mirror yet.
+    pub added: usize,
+    /// People edited in Swapi since the previous sync.
+    pub updated: usize,
+    /// People that didn't change since the previous sync.
+    pub unchanged: usize,
+}
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS people (
+        url TEXT PRIMARY KEY,
+        id INTEGER,
+        name TEXT NOT NULL,
+        edited TEXT NOT NULL,
+        person TEXT NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS sync (
+        id INTEGER PRIMARY KEY CHECK (id = 0),
+        synced_at TEXT NOT NULL
+    );
+";
+
+impl SqliteMirror {
+    /// Open the mirror at the given path, creating it if it doesn't exist.
+    pub fn open(path: &Path) -> anyhow::Result<Self> {
+        let connection = Connection::open(path)
+            .with_context(|| format!("cannot open people database {:?}", path))?;
+        connection
+            .execute_batch(SCHEMA)
+            .context("cannot create people database schema")?;
+        Ok(Self {
+            connection: Arc::new(Mutex::new(connection)),
+        })
+    }
+
+    /// Copy all the people of Swapi into the mirror.
+    /// People whose `edited` timestamp didn't change since the previous sync are left untouched.
+    pub async fn sync(&self, swapi_client: &SwapiClient) -> anyhow::Result<SyncReport> {
+        let people = swapi_client
+            .all_people()
+            .await
+            .context("cannot retrieve people from Swapi")?;
+        let report = self
+            .with_connection(move |connection| store(connection, &people))
+            .await
+            .context("cannot store people")?;
+        tracing::info!("synced people: {report:?}");
+        Ok(report)
+    }
+
+    /// Run the given function on the connection, without blocking the async runtime.
+    async fn with_connection<T, F>(&self, f: F) -> Result<T, rusqlite::Error>
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut Connection) -> Result<T, rusqlite::Error> + Send + 'static,
+    {
+        let connection = Arc::clone(&self.connection);
+        tokio::task::spawn_blocking(move || f(&mut connection.lock().unwrap()))
+            .await
+            .expect("people database task panicked")
+    }
+}
+
+fn store(connection: &mut Connection, people: &[Person]) -> Result<SyncReport, rusqlite::Error> {
+    let transaction = connection.transaction()?;
+    let mut report = SyncReport::default();
+    for person in people {
+        let stored_edited: Option<DateTime<Utc>> = transaction
+            .query_row(
+                "SELECT edited FROM people WHERE url = ?1",
+                params![person.url],
+                |row| row.get(0),
+            )
+            .optional()?;
+        match stored_edited {
+            Some(edited) if edited >= person.edited => {
+                report.unchanged += 1;
+                continue;
+            }
+            Some(_) => report.updated += 1,
+            None => report.added += 1,
+        }
+        let json = serde_json::to_string(person)
+            .map_err(|e| rusqlite::Error::ToSqlConversionFailure(e.into()))?;
+        transaction.execute(
+            "INSERT OR REPLACE INTO people (url, id, name, edited, person)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![person.url, person.id(), person.name, person.edited, json],
+        )?;
+    }
+    transaction.execute(
+        "INSERT OR REPLACE INTO sync (id, synced_at) VALUES (0, ?1)",
+        params![Utc::now()],
+    )?;
+    transaction.commit()?;
+    Ok(report)
+}
+
+#[async_trait]
+impl PeopleSource for SqliteMirror {
+    async fn people_by_name(&self, name: &str) -> Result<Vec<Person>, YodaTallerError> {
+        let name = name.to_string();
+        let people = self
+            .with_connection(move |connection| {
+                let mut statement = connection.prepare(
+                    "SELECT person FROM people
+                     WHERE instr(lower(name), lower(?1)) > 0
+                     ORDER BY id",
+                )?;
+                let people = statement
+                    .query_map(params![name], person_from_row)?
+                    .collect::<Result<Vec<Person>, _>>()?;
+                Ok(people)
+            })
+            .await?;
+        Ok(people)
+    }
+
+    async fn all_people(&self) -> Result<Vec<Person>, YodaTallerError> {
+        let people = self
+            .with_connection(|connection| {
+                let mut statement = connection.prepare("SELECT person FROM people ORDER BY id")?;
+                let people = statement
+                    .query_map([], person_from_row)?
+                    .collect::<Result<Vec<Person>, _>>()?;
+                Ok(people)
+            })
+            .await?;
+        Ok(people)
+    }
+
+    async fn synced_at(&self) -> Option<DateTime<Utc>> {
+        let synced_at = self
+            .with_connection(|connection| {
+                connection
+                    .query_row("SELECT synced_at FROM sync WHERE id = 0", [], |row| {
+                        row.get(0)
+                    })
+                    .optional()
+            })
+            .await;
+        synced_at.unwrap_or_else(|e| {
+            tracing::error!("cannot read the last sync time: {e}");
+            None
+        })
+    }
+}
+
+/// Person stored as json in the first column of the row.
+fn person_from_row(row: &rusqlite::Row) -> Result<Person, rusqlite::Error> {
+    let json: String = row.get(0)?;
+    serde_json::from_str(&json).map_err(|e| {
+        rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Text, e.into())
+    })
+}
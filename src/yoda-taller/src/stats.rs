@@ -0,0 +1,162 @@
+//! Statistics about the heights of all the people known by Swapi.
+//!
+//! Walking every people page takes a while, so statistics are computed in the background
+//! and the latest ones are served until the next refresh.
+
+use {
+    crate::{
+        height::Height,
+        swapi::Person,
+        taller::{Character, YodaTaller},
+    },
+    chrono::{DateTime, Utc},
+    std::{
+        sync::{Arc, RwLock},
+        time::Duration,
+    },
+};
+
+/// Distribution of the heights of the people known by Swapi.
+#[derive(Debug, Clone, serde::Serialize)]
+// derive only for tests
+#[cfg_attr(feature = "test_fixture", derive(serde::Deserialize, PartialEq))]
+pub struct HeightStats {
+    /// Number of people with a known height.
+    pub count: usize,
+    /// Shortest character.
+    pub min: Option<Character>,
+    /// Tallest character.
+    pub max: Option<Character>,
+    /// Median height in centimeters.
+    pub median_cm: Option<f64>,
+    /// Number of people by height range, from the shortest range to the tallest one.
+    pub histogram: Vec<Bucket>,
+    /// Percentage of people shorter than Yoda, counting people as tall as him for a half.
+    pub yoda_percentile: Option<f64>,
+    /// Names of the people whose height is unknown.
+    pub unknown_height: Vec<String>,
+    /// When these statistics were computed.
+    pub computed_at: DateTime<Utc>,
+}
+
+/// Number of people whose height is in `[from_cm, to_cm)`.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+// derive only for tests
+#[cfg_attr(feature = "test_fixture", derive(serde::Deserialize))]
+pub struct Bucket {
+    pub from_cm: u32,
+    pub to_cm: u32,
+    pub count: usize,
+}
+
+impl HeightStats {
+    /// Compute the statistics of the given people,
+    /// grouping heights in histogram buckets of the given width.
+    pub fn compute(people: Vec<Person>, bucket_width_cm: u32) -> Self {
+        let bucket_width_cm = bucket_width_cm.max(1);
+        let (known, unknown): (Vec<Person>, Vec<Person>) = people
+            .into_iter()
+            .partition(|person| person.height.is_some());
+        let mut characters: Vec<Character> = known
+            .into_iter()
+            .filter_map(|person| Character::try_from(person).ok())
+            .collect();
+        characters.sort_by_key(|character| character.height);
+        let heights: Vec<u32> = characters.iter().map(|c| c.height.cm()).collect();
+
+        Self {
+            count: characters.len(),
+            median_cm: median(&heights),
+            histogram: histogram(&heights, bucket_width_cm),
+            yoda_percentile: percentile(&heights, Character::yoda().height),
+            min: characters.first().cloned(),
+            max: characters.last().cloned(),
+            unknown_height: unknown.into_iter().map(|person| person.name).collect(),
+            computed_at: Utc::now(),
+        }
+    }
+}
+
+/// Median of the given sorted heights.
+fn median(heights: &[u32]) -> Option<f64> {
+    let middle = heights.len() / 2;
+    match heights.len() {
+        0 => None,
+        len if len % 2 == 0 => Some(f64::from(heights[middle - 1] + heights[middle]) / 2.0),
+        _ => Some(f64::from(heights[middle])),
+    }
+}
+
+/// Histogram of the given sorted heights, without gaps between the first and the last bucket.
+fn histogram(heights: &[u32], bucket_width_cm: u32) -> Vec<Bucket> {
+    let (Some(min), Some(max)) = (heights.first(), heights.last()) else {
+        return vec![];
+    };
+    let first_bucket = min / bucket_width_cm;
+    let last_bucket = max / bucket_width_cm;
+    (first_bucket..=last_bucket)
+        .map(|bucket| {
+            let from_cm = bucket * bucket_width_cm;
+            let to_cm = from_cm + bucket_width_cm;
+            let count = heights
+                .iter()
+                .filter(|height| (from_cm..to_cm).contains(height))
+                .count();
+            Bucket {
+                from_cm,
+                to_cm,
+                count,
+            }
+        })
+        .collect()
+}
+
+/// Percentile rank of the given height among the given heights.
+fn percentile(heights: &[u32], height: Height) -> Option<f64> {
+    if heights.is_empty() {
+        return None;
+    }
+    let shorter = heights.iter().filter(|h| **h < height.cm()).count();
+    let equal = heights.iter().filter(|h| **h == height.cm()).count();
+    Some((shorter as f64 + equal as f64 / 2.0) * 100.0 / heights.len() as f64)
+}
+
+/// Latest height statistics, refreshed in the background.
+#[derive(Debug, Default)]
+pub struct HeightStatsCache {
+    latest: RwLock<Option<HeightStats>>,
+}
+
+impl HeightStatsCache {
+    /// Latest statistics, if they have been computed at least once.
+    pub fn latest(&self) -> Option<HeightStats> {
+        self.latest.read().unwrap().clone()
+    }
+
+    /// Compute the statistics now and then once every `interval`.
+    /// A zero `interval` computes them once, without ever refreshing them.
+    /// Statistics that can't be computed are logged and the previous ones are kept.
+    pub async fn refresh_periodically(
+        self: Arc<Self>,
+        yoda_taller: Arc<YodaTaller>,
+        bucket_width_cm: u32,
+        interval: Duration,
+    ) {
+        if interval.is_zero() {
+            self.refresh(&yoda_taller, bucket_width_cm).await;
+            return;
+        }
+        let mut interval = tokio::time::interval(interval);
+        loop {
+            interval.tick().await;
+            self.refresh(&yoda_taller, bucket_width_cm).await;
+        }
+    }
+
+    async fn refresh(&self, yoda_taller: &YodaTaller, bucket_width_cm: u32) {
+        match yoda_taller.height_stats(bucket_width_cm).await {
+            Ok(stats) => *self.latest.write().unwrap() = Some(stats),
+            Err(e) => tracing::error!("cannot refresh height statistics: {e}"),
+        }
+    }
+}
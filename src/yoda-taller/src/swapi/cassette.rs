@@ -0,0 +1,137 @@
+//! Record the responses of Swapi to replay them later, without network.
+
+use {
+    anyhow::Context,
+    http::StatusCode,
+    reqwest::Response,
+    std::{
+        collections::HashMap,
+        fs::{File, OpenOptions},
+        io::{BufRead, BufReader, Write},
+        path::Path,
+        sync::Mutex,
+    },
+};
+
+/// How the Swapi client uses its cassette.
+#[derive(serde::Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CassetteMode {
+    /// Call Swapi without recording anything.
+    #[default]
+    Passthrough,
+    /// Call Swapi and save each request with its response to the cassette.
+    Record,
+    /// Answer with the responses saved in the cassette, without calling Swapi.
+    Replay,
+}
+
+/// File of Swapi requests and responses, one json object per line.
+pub enum Cassette {
+    Record(Mutex<File>),
+    Replay(HashMap<String, Recorded>),
+}
+
+/// A request to Swapi, with its response.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct Interaction {
+    /// Method and path of the request, such as `GET /api/people/?search=yoda`.
+    pub request: String,
+    pub status: u16,
+    pub body: String,
+}
+
+/// Response of a loaded [`Interaction`], whose status is known to be valid.
+pub struct Recorded {
+    status: StatusCode,
+    body: String,
+}
+
+impl Cassette {
+    /// Open the cassette at the given path to record responses to it,
+    /// after the ones it already contains.
+    pub fn for_recording(path: &Path) -> anyhow::Result<Self> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .with_context(|| format!("cannot open cassette {:?}", path))?;
+        Ok(Self::Record(Mutex::new(file)))
+    }
+
+    /// Load the responses recorded in the cassette at the given path, to replay them.
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let file = File::open(path).with_context(|| format!("cannot open cassette {:?}", path))?;
+        let mut interactions = HashMap::new();
+        for line in BufReader::new(file).lines() {
+            let line = line.with_context(|| format!("cannot read cassette {:?}", path))?;
+            let interaction: Interaction = serde_json::from_str(&line)
+                .with_context(|| format!("invalid cassette format in {:?}", path))?;
+            let status = StatusCode::from_u16(interaction.status).with_context(|| {
+                format!(
+                    "invalid status {} of {} in {:?}",
+                    interaction.status, interaction.request, path
+                )
+            })?;
+            let recorded = Recorded {
+                status,
+                body: interaction.body,
+            };
+            interactions.insert(interaction.request, recorded);
+        }
+        Ok(Self::Replay(interactions))
+    }
+
+    /// Save the response to the request for the given url, if recording,
+    /// and return an equivalent response.
+    pub async fn record(&self, url: &str, response: Response) -> Result<Response, reqwest::Error> {
+        let Self::Record(file) = self else {
+            return Ok(response);
+        };
+        let status = response.status();
+        let body = response.text().await?;
+        let interaction = Interaction {
+            request: request_key(url),
+            status: status.as_u16(),
+            body,
+        };
+        let saved = serde_json::to_string(&interaction)
+            .map_err(anyhow::Error::from)
+            .and_then(|line| Ok(writeln!(file.lock().unwrap(), "{line}")?));
+        if let Err(e) = saved {
+            tracing::error!("cannot record {}: {e}", interaction.request);
+        }
+        Ok(swapi_response(status, interaction.body))
+    }
+
+    /// The recorded response to the request for the given url, if replaying.
+    /// Requests that weren't recorded get a `404 Not Found` response.
+    pub fn replay(&self, url: &str) -> Option<Response> {
+        let Self::Replay(interactions) = self else {
+            return None;
+        };
+        let request = request_key(url);
+        let response = match interactions.get(&request) {
+            Some(recorded) => swapi_response(recorded.status, recorded.body.clone()),
+            None => {
+                tracing::warn!("no recorded response to {request}");
+                swapi_response(StatusCode::NOT_FOUND, String::new())
+            }
+        };
+        Some(response)
+    }
+}
+
+/// Response with the given status and body, as if it came from Swapi.
+fn swapi_response(status: StatusCode, body: String) -> Response {
+    let mut response = http::Response::new(body);
+    *response.status_mut() = status;
+    response.into()
+}
+
+/// Identify the request for the given url, independently of the host of Swapi,
+/// so that a cassette can be replayed with another base url than the one it was recorded with.
+fn request_key(url: &str) -> String {
+    let path = url.find("/api/").map_or(url, |api_start| &url[api_start..]);
+    format!("GET {path}")
+}
@@ -0,0 +1,36 @@
+use {
+    chrono::{DateTime, NaiveDate, Utc},
+    serde::{Deserialize, Serialize},
+};
+
+/// A Star Wars film, as described by the Swapi films resource.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Film {
+    pub title: String,
+    pub episode_id: u32,
+    pub opening_crawl: String,
+    pub director: String,
+    pub producer: String,
+    pub release_date: NaiveDate,
+    /// Urls of the people appearing in this film.
+    pub characters: Vec<String>,
+    /// Urls of the planets appearing in this film.
+    pub planets: Vec<String>,
+    /// Urls of the starships appearing in this film.
+    pub starships: Vec<String>,
+    /// Urls of the vehicles appearing in this film.
+    pub vehicles: Vec<String>,
+    /// Urls of the species appearing in this film.
+    pub species: Vec<String>,
+    pub created: DateTime<Utc>,
+    pub edited: DateTime<Utc>,
+    /// Url of this film in Swapi.
+    pub url: String,
+}
+
+impl Film {
+    /// Swapi identifier of this film, taken from the last segment of its url.
+    pub fn id(&self) -> Option<u32> {
+        super::resource_id(&self.url)
+    }
+}
@@ -0,0 +1,191 @@
+//! Client to call the swapi API.
+
+mod cassette;
+mod film;
+mod person;
+mod planet;
+mod species;
+mod unknown;
+
+use {
+    anyhow::Context,
+    futures::future::try_join_all,
+    reqwest::Client,
+    serde::{de::DeserializeOwned, Deserialize},
+    std::{sync::Arc, time::Duration},
+    tracing::instrument,
+};
+pub use {
+    cassette::{Cassette, CassetteMode, Interaction},
+    film::Film,
+    person::{Mass, ParseMassError, Person},
+    planet::Planet,
+    species::Species,
+};
+
+/// A page of results of a Swapi search.
+#[derive(Debug, PartialEq, Deserialize)]
+pub struct Query<T> {
+    /// Total number of results, across all pages.
+    pub count: u32,
+    /// Url of the next page, if any.
+    pub next: Option<String>,
+    /// Url of the previous page, if any.
+    pub previous: Option<String>,
+    pub results: Vec<T>,
+}
+
+#[derive(Clone)]
+pub struct SwapiClient {
+    http_client: Client,
+    base_url: String,
+    /// Maximum number of pages to retrieve when following pagination.
+    /// The first page is always retrieved.
+    max_pages: usize,
+    /// Cassette to record responses to, or to replay them from.
+    cassette: Option<Arc<Cassette>>,
+}
+
+impl SwapiClient {
+    pub fn new(base_url: String, timeout: Duration, max_pages: usize) -> anyhow::Result<Self> {
+        let http_client = Client::builder()
+            .timeout(timeout)
+            .build()
+            .context("can't build http client")?;
+        Ok(Self {
+            http_client,
+            base_url,
+            max_pages: max_pages.max(1),
+            cassette: None,
+        })
+    }
+
+    /// Record the responses of Swapi to the given cassette, or replay them from it.
+    pub fn with_cassette(self, cassette: Cassette) -> Self {
+        Self {
+            cassette: Some(Arc::new(cassette)),
+            ..self
+        }
+    }
+
+    /// First page of people whose name contains the given one.
+    #[instrument(skip(self))]
+    pub async fn people_page_by_name(&self, name: &str) -> Result<Vec<Person>, reqwest::Error> {
+        let query: Query<Person> = self.get(&self.people_search_url(name)).await?;
+        Ok(query.results)
+    }
+
+    /// All the people whose name contains the given one,
+    /// following the `next` links for at most `max_pages` pages.
+    #[instrument(skip(self))]
+    pub async fn all_people_by_name(&self, name: &str) -> Result<Vec<Person>, reqwest::Error> {
+        self.people_pages(self.people_search_url(name), Some(self.max_pages))
+            .await
+    }
+
+    /// All the people known by Swapi, following every result page.
+    #[instrument(skip(self))]
+    pub async fn all_people(&self) -> Result<Vec<Person>, reqwest::Error> {
+        self.people_pages(format!("{}/api/people/", self.base_url), None)
+            .await
+    }
+
+    /// People of the result page with the given url and of the pages after it,
+    /// up to `max_pages` pages.
+    async fn people_pages(
+        &self,
+        first_page_url: String,
+        max_pages: Option<usize>,
+    ) -> Result<Vec<Person>, reqwest::Error> {
+        let mut people = vec![];
+        let mut next = Some(first_page_url);
+        let mut pages = 0;
+        while let Some(url) = next {
+            if Some(pages) == max_pages {
+                tracing::warn!("page limit of {pages} reached, ignoring {url}");
+                break;
+            }
+            let query: Query<Person> = self.get(&url).await?;
+            people.extend(query.results);
+            next = query.next.map(|url| self.resource_url(&url));
+            pages += 1;
+        }
+        Ok(people)
+    }
+
+    /// Films whose title contains the given text.
+    #[instrument(skip(self))]
+    pub async fn films_by_title(&self, title: &str) -> Result<Vec<Film>, reqwest::Error> {
+        let url = format!("{}/api/films/?search={title}", self.base_url);
+        let query: Query<Film> = self.get(&url).await?;
+        Ok(query.results)
+    }
+
+    /// Characters appearing in the film, fetched concurrently.
+    #[instrument(skip_all, fields(film = film.title))]
+    pub async fn film_characters(&self, film: &Film) -> Result<Vec<Person>, reqwest::Error> {
+        try_join_all(film.characters.iter().map(|url| self.person(url))).await
+    }
+
+    /// Person linked by the given url, such as a member of a species.
+    #[instrument(skip(self))]
+    pub async fn person(&self, url: &str) -> Result<Person, reqwest::Error> {
+        self.get(&self.resource_url(url)).await
+    }
+
+    /// Planet linked by the given url, such as a person's homeworld.
+    #[instrument(skip(self))]
+    pub async fn planet(&self, url: &str) -> Result<Planet, reqwest::Error> {
+        self.get(&self.resource_url(url)).await
+    }
+
+    /// Species linked by the given url.
+    #[instrument(skip(self))]
+    pub async fn species(&self, url: &str) -> Result<Species, reqwest::Error> {
+        self.get(&self.resource_url(url)).await
+    }
+
+    /// Film linked by the given url.
+    #[instrument(skip(self))]
+    pub async fn film(&self, url: &str) -> Result<Film, reqwest::Error> {
+        self.get(&self.resource_url(url)).await
+    }
+
+    fn people_search_url(&self, name: &str) -> String {
+        format!("{}/api/people/?search={name}", self.base_url)
+    }
+
+    /// Url of a linked resource or result page on the configured Swapi instance.
+    ///
+    /// Swapi links resources with absolute urls built from its own host name,
+    /// which isn't necessarily reachable with the same name from here.
+    fn resource_url(&self, url: &str) -> String {
+        match url.find("/api/") {
+            Some(api_start) => format!("{}{}", self.base_url, &url[api_start..]),
+            None => url.to_string(),
+        }
+    }
+
+    async fn get<T: DeserializeOwned>(&self, url: &str) -> Result<T, reqwest::Error> {
+        let cassette = self.cassette.as_deref();
+        let response = match cassette.and_then(|cassette| cassette.replay(url)) {
+            Some(response) => response,
+            None => {
+                let response = self.http_client.get(url).send().await?;
+                match cassette {
+                    Some(cassette) => cassette.record(url, response).await?,
+                    None => response,
+                }
+            }
+        };
+        response.error_for_status()?.json().await
+    }
+}
+
+/// Swapi identifier of a resource, taken from the last segment of its url.
+fn resource_id(url: &str) -> Option<u32> {
+    url.trim_end_matches('/')
+        .rsplit('/')
+        .next()
+        .and_then(|id| id.parse().ok())
+}
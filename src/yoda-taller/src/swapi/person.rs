@@ -0,0 +1,91 @@
+use {
+    super::unknown,
+    crate::{birth_year::BirthYear, height::Height},
+    chrono::{DateTime, Utc},
+    serde::{Deserialize, Serialize},
+    std::{fmt, str::FromStr},
+};
+
+/// A person of the Star Wars universe, as described by the Swapi people resource.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Person {
+    pub name: String,
+    #[serde(with = "unknown")]
+    pub height: Option<Height>,
+    #[serde(with = "unknown")]
+    pub mass: Option<Mass>,
+    #[serde(with = "unknown")]
+    pub hair_color: Option<String>,
+    #[serde(with = "unknown")]
+    pub skin_color: Option<String>,
+    #[serde(with = "unknown")]
+    pub eye_color: Option<String>,
+    /// Birth year, relative to the Battle of Yavin, such as `19BBY`.
+    #[serde(with = "unknown")]
+    pub birth_year: Option<BirthYear>,
+    #[serde(with = "unknown")]
+    pub gender: Option<String>,
+    /// Url of the planet this person was born on.
+    pub homeworld: String,
+    /// Urls of the films this person appears in.
+    pub films: Vec<String>,
+    /// Urls of the species this person belongs to.
+    pub species: Vec<String>,
+    /// Urls of the vehicles this person has piloted.
+    pub vehicles: Vec<String>,
+    /// Urls of the starships this person has piloted.
+    pub starships: Vec<String>,
+    pub created: DateTime<Utc>,
+    pub edited: DateTime<Utc>,
+    /// Url of this person in Swapi.
+    pub url: String,
+}
+
+impl Person {
+    /// Swapi identifier of this person, taken from the last segment of its url.
+    pub fn id(&self) -> Option<u32> {
+        super::resource_id(&self.url)
+    }
+}
+
+/// Mass of a person in kilograms.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Serialize)]
+// derive only for tests
+#[cfg_attr(feature = "test_fixture", derive(Deserialize))]
+#[serde(transparent)]
+pub struct Mass {
+    kg: f64,
+}
+
+impl Mass {
+    pub const fn from_kg(kg: f64) -> Self {
+        Self { kg }
+    }
+
+    pub fn kg(self) -> f64 {
+        self.kg
+    }
+}
+
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+#[error("invalid mass")]
+pub struct ParseMassError;
+
+impl FromStr for Mass {
+    type Err = ParseMassError;
+
+    /// Parse a mass in kilograms, such as `"78.2"` or `"1,358"`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let kg: f64 = s.replace(',', "").parse().map_err(|_| ParseMassError)?;
+        if !kg.is_finite() || kg < 0.0 {
+            return Err(ParseMassError);
+        }
+        Ok(Self::from_kg(kg))
+    }
+}
+
+impl fmt::Display for Mass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.kg.fmt(f)
+    }
+}
@@ -0,0 +1,36 @@
+use {
+    super::unknown,
+    chrono::{DateTime, Utc},
+    serde::{Deserialize, Serialize},
+};
+
+/// A planet of the Star Wars universe, as described by the Swapi planets resource.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Planet {
+    pub name: String,
+    /// Hours it takes to complete a rotation.
+    #[serde(with = "unknown")]
+    pub rotation_period: Option<u32>,
+    /// Days it takes to complete an orbit around its star.
+    #[serde(with = "unknown")]
+    pub orbital_period: Option<u32>,
+    /// Diameter in kilometers.
+    #[serde(with = "unknown")]
+    pub diameter: Option<u32>,
+    pub climate: String,
+    pub gravity: String,
+    pub terrain: String,
+    /// Percentage of the surface covered by water.
+    #[serde(with = "unknown")]
+    pub surface_water: Option<f64>,
+    #[serde(with = "unknown")]
+    pub population: Option<u64>,
+    /// Urls of the people born on this planet.
+    pub residents: Vec<String>,
+    /// Urls of the films this planet appears in.
+    pub films: Vec<String>,
+    pub created: DateTime<Utc>,
+    pub edited: DateTime<Utc>,
+    /// Url of this planet in Swapi.
+    pub url: String,
+}
@@ -0,0 +1,32 @@
+use {
+    super::unknown,
+    crate::height::Height,
+    chrono::{DateTime, Utc},
+    serde::{Deserialize, Serialize},
+};
+
+/// A species of the Star Wars universe, as described by the Swapi species resource.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Species {
+    pub name: String,
+    pub classification: String,
+    pub designation: String,
+    #[serde(with = "unknown")]
+    pub average_height: Option<Height>,
+    pub skin_colors: String,
+    pub hair_colors: String,
+    pub eye_colors: String,
+    /// Average lifespan in years, or a description such as `indefinite`.
+    pub average_lifespan: String,
+    /// Url of the planet this species comes from.
+    pub homeworld: Option<String>,
+    pub language: String,
+    /// Urls of the people of this species.
+    pub people: Vec<String>,
+    /// Urls of the films this species appears in.
+    pub films: Vec<String>,
+    pub created: DateTime<Utc>,
+    pub edited: DateTime<Utc>,
+    /// Url of this species in Swapi.
+    pub url: String,
+}
@@ -0,0 +1,41 @@
+//! (De)serialize Swapi values that can be `unknown` or `n/a`, such as heights.
+//! Invalid values are considered unknown.
+
+use {
+    serde::{Deserialize, Deserializer, Serializer},
+    std::{fmt::Display, str::FromStr},
+};
+
+const UNKNOWN: &str = "unknown";
+const NOT_APPLICABLE: &str = "n/a";
+
+pub fn deserialize<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr,
+    T::Err: Display,
+{
+    let value = String::deserialize(deserializer)?;
+    match value.as_str() {
+        UNKNOWN | NOT_APPLICABLE => Ok(None),
+        // An invalid value of a person must not fail the whole page of results.
+        _ => match value.parse() {
+            Ok(value) => Ok(Some(value)),
+            Err(e) => {
+                tracing::warn!("ignoring invalid value {value:?}: {e}");
+                Ok(None)
+            }
+        },
+    }
+}
+
+pub fn serialize<S, T>(value: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    T: Display,
+{
+    match value {
+        Some(value) => serializer.collect_str(value),
+        None => serializer.serialize_str(UNKNOWN),
+    }
+}
@@ -1,6 +1,25 @@
-use {crate::swapi::SwapiClient, std::time::Duration, tracing::instrument};
+use {
+    crate::{
+        attribute::{Attribute, AttributeComparison},
+        birth_year::{BirthYear, Era},
+        estimate::{self, Estimate, EstimateBasis},
+        expand::{Expanded, Links},
+        height::Height,
+        ranking::{self, MatchMode},
+        source::{CacheStats, CircuitState, PeopleSource, SingleFlight},
+        stats::HeightStats,
+        swapi::{Film, Person, SwapiClient},
+    },
+    chrono::{DateTime, Utc},
+    futures::{stream, StreamExt},
+    std::{cmp::Reverse, collections::HashMap, sync::Arc, time::Duration},
+    tracing::instrument,
+};
 
 pub struct YodaTaller {
+    /// Where people are looked up by name.
+    people: Arc<dyn PeopleSource>,
+    /// Client used to resolve the resources linked from people, and films.
     swapi_client: SwapiClient,
 }
 
@@ -12,48 +31,618 @@ pub struct YodaTallerOutcome {
     pub person: String,
     /// Whether Yoda is taller than this character or not.
     pub taller: bool,
+    /// How Yoda's height relates to the height of this character.
+    pub relation: Relation,
+    /// Yoda's height in centimeters.
+    pub yoda_height: Height,
+    /// Height of this character in centimeters.
+    pub person_height: Height,
+    /// How many centimeters Yoda is taller than this character.
+    /// Negative if Yoda is shorter.
+    pub difference_cm: i64,
+    /// Whether the height of this character is estimated, because Swapi doesn't know it.
+    pub estimated: bool,
+    /// What the estimated height of this character is based on.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub basis: Option<EstimateBasis>,
 }
 
-#[derive(thiserror::Error, Debug)]
+/// Outcome of [`YodaTaller::is_taller_than_with`].
+#[derive(Debug)]
+pub struct TallerLookup {
+    pub outcome: YodaTallerOutcome,
+    /// Resources linked from the person, if asked.
+    pub expanded: Option<Expanded>,
+    /// How long ago the person was retrieved, if Swapi failed and a stale copy was used.
+    pub stale_for: Option<Duration>,
+}
+
+impl From<Comparison> for YodaTallerOutcome {
+    /// Outcome of a comparison between Yoda, the first character, and another one.
+    fn from(comparison: Comparison) -> Self {
+        let Comparison {
+            first: yoda,
+            second: person,
+            relation,
+        } = comparison;
+        Self {
+            taller: relation == Relation::Taller,
+            relation,
+            difference_cm: i64::from(yoda.height.cm()) - i64::from(person.height.cm()),
+            yoda_height: yoda.height,
+            person_height: person.height,
+            person: person.name,
+            estimated: false,
+            basis: None,
+        }
+    }
+}
+
+/// Is Yoda older than a person?
+#[derive(Debug, serde::Serialize)]
+// derive only for tests
+#[cfg_attr(feature = "test_fixture", derive(serde::Deserialize, PartialEq))]
+pub struct YodaOlderOutcome {
+    /// Name of the person to compare with Yoda.
+    pub person: String,
+    /// Whether Yoda was born before this character or not.
+    pub older: bool,
+    pub yoda_birth_year: BirthYear,
+    pub person_birth_year: BirthYear,
+}
+
+/// Yoda was born 896 years before the Battle of Yavin.
+pub fn yoda_birth_year() -> BirthYear {
+    BirthYear::new(896.0, Era::Bby)
+}
+
+/// A Star Wars character with a known height.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+// derive only for tests
+#[cfg_attr(feature = "test_fixture", derive(serde::Deserialize))]
+pub struct Character {
+    /// Full name of the character, as returned by Swapi.
+    pub name: String,
+    pub height: Height,
+}
+
+impl Character {
+    /// Yoda himself.
+    pub fn yoda() -> Self {
+        Self {
+            name: "Yoda".to_string(),
+            height: Height::from_cm(66),
+        }
+    }
+}
+
+impl TryFrom<Person> for Character {
+    type Error = YodaTallerError;
+
+    fn try_from(person: Person) -> Result<Self, Self::Error> {
+        let height = person.height.ok_or_else(|| {
+            tracing::warn!("unknown height");
+            YodaTallerError::HeightNotFound
+        })?;
+        Ok(Self {
+            name: person.name,
+            height,
+        })
+    }
+}
+
+/// How an attribute of a character, such as the height, relates to the one of another character.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+// derive only for tests
+#[cfg_attr(feature = "test_fixture", derive(serde::Deserialize))]
+pub enum Relation {
+    Taller,
+    Shorter,
+    Heavier,
+    Lighter,
+    Older,
+    Younger,
+    Equal,
+}
+
+#[derive(Debug, serde::Serialize)]
+// derive only for tests
+#[cfg_attr(feature = "test_fixture", derive(serde::Deserialize, PartialEq, Eq))]
+pub struct Comparison {
+    pub first: Character,
+    pub second: Character,
+    /// How `first` relates to `second`.
+    pub relation: Relation,
+}
+
+impl Comparison {
+    pub fn between(first: Character, second: Character) -> Self {
+        let relation = Attribute::Height.relation(first.height.cmp(&second.height));
+        Self {
+            first,
+            second,
+            relation,
+        }
+    }
+}
+
+/// One of the people matching an ambiguous name, or of the films matching an ambiguous title.
+#[derive(Debug, Clone, serde::Serialize)]
+// derive only for tests
+#[cfg_attr(feature = "test_fixture", derive(serde::Deserialize, PartialEq, Eq))]
+pub struct Candidate {
+    /// Swapi identifier of the person or film.
+    pub id: Option<u32>,
+    /// Full name of the person, or title of the film.
+    pub name: String,
+}
+
+impl From<&Film> for Candidate {
+    fn from(film: &Film) -> Self {
+        Self {
+            id: film.id(),
+            name: film.title.clone(),
+        }
+    }
+}
+
+impl From<&Person> for Candidate {
+    fn from(person: &Person) -> Self {
+        Self {
+            id: person.id(),
+            name: person.name.clone(),
+        }
+    }
+}
+
+/// Which person to pick when a name matches several people.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Pick {
+    /// Fail with [`YodaTallerError::Ambiguous`] if there are several best matches.
+    #[default]
+    Unique,
+    /// Pick the first of the best matches, in the order returned by Swapi.
+    First,
+}
+
+/// Options to resolve a name into a person.
+#[derive(Debug, Default, Clone, serde::Deserialize)]
+pub struct LookupOptions {
+    #[serde(default)]
+    pub pick: Pick,
+    #[serde(default, rename = "match")]
+    pub match_mode: MatchMode,
+}
+
+/// Errors are cheap to clone, so that a search can be shared by concurrent requests.
+#[derive(thiserror::Error, Debug, Clone)]
 pub enum YodaTallerError {
     /// The person doesn't have a known or valid height.
     #[error("Person's height is unknown")]
     HeightNotFound,
+    /// The person doesn't have a known or valid mass.
+    #[error("Person's mass is unknown")]
+    MassNotFound,
+    /// The person doesn't have a known or valid birth year.
+    #[error("Person's birth year is unknown")]
+    BirthYearNotFound,
     /// No person with the given name exists.
     #[error("Person not found")]
     PersonNotFound,
+    /// No film with the given title exists.
+    #[error("Film not found")]
+    FilmNotFound,
+    /// More than one person matches the given name.
+    #[error("Multiple people match the name")]
+    Ambiguous { candidates: Vec<Candidate> },
+    /// More than one film matches the given title.
+    #[error("Multiple films match the title")]
+    AmbiguousFilm { candidates: Vec<Candidate> },
+    /// Swapi failed too many times in a row, so that it isn't called for a while.
+    #[error("Swapi is unavailable")]
+    CircuitOpen { retry_after: Duration },
     /// Unexpected error while calling Swapi API.
     #[error("Unexpected error while retrieving person height: {0}")]
-    UnexpectedError(#[from] reqwest::Error),
+    UnexpectedError(#[source] Arc<reqwest::Error>),
+    /// Unexpected error while reading the local copy of Swapi.
+    #[error("Unexpected error while reading the people database: {0}")]
+    DatabaseError(#[source] Arc<rusqlite::Error>),
+}
+
+impl From<reqwest::Error> for YodaTallerError {
+    fn from(e: reqwest::Error) -> Self {
+        Self::UnexpectedError(Arc::new(e))
+    }
+}
+
+impl From<rusqlite::Error> for YodaTallerError {
+    fn from(e: rusqlite::Error) -> Self {
+        Self::DatabaseError(Arc::new(e))
+    }
+}
+
+/// Characters of a film that are shorter than Yoda.
+#[derive(Debug)]
+pub struct FilmComparison {
+    /// Title of the film.
+    pub film: String,
+    /// Characters shorter than Yoda, and characters that couldn't be compared with him,
+    /// in the order returned by Swapi.
+    pub characters: Vec<Result<Character, ComparisonError>>,
+}
+
+/// Characters sorted by height, from the tallest to the shortest.
+#[derive(Debug)]
+pub struct HeightRanking {
+    /// Ranked characters, Yoda included.
+    pub ranked: Vec<RankedCharacter>,
+    /// Names that couldn't be ranked, in the order they were given.
+    pub failures: Vec<ComparisonError>,
+}
+
+/// A character in a [`HeightRanking`].
+#[derive(Debug, Clone, serde::Serialize)]
+// derive only for tests
+#[cfg_attr(feature = "test_fixture", derive(serde::Deserialize, PartialEq, Eq))]
+pub struct RankedCharacter {
+    /// Position in the ranking, starting from 1 for the tallest character.
+    pub position: usize,
+    #[serde(flatten)]
+    pub character: Character,
+    /// Whether this character is Yoda.
+    pub yoda: bool,
+}
+
+/// Heights of some characters compared with each other.
+#[derive(Debug)]
+pub struct HeightMatrix {
+    /// How the character of each name relates to the character of each name,
+    /// in the order of the names. `None` if either of them couldn't be resolved.
+    pub relations: Vec<Vec<Option<Relation>>>,
+    /// Names that couldn't be resolved, each reported once.
+    pub failures: Vec<ComparisonError>,
+}
+
+/// Error of a comparison, together with the name that caused it.
+#[derive(thiserror::Error, Debug)]
+#[error("{error}")]
+pub struct ComparisonError {
+    /// Name of the person that couldn't be compared.
+    pub query: String,
+    #[source]
+    pub error: YodaTallerError,
 }
 
 impl YodaTaller {
-    pub fn new(swapi_base_url: String, swapi_timeout: Duration) -> anyhow::Result<Self> {
-        let swapi_client = SwapiClient::new(swapi_base_url, swapi_timeout)?;
-        Ok(Self { swapi_client })
+    /// Concurrent searches of the same name share one search of `people`.
+    pub fn new(people: Arc<dyn PeopleSource>, swapi_client: SwapiClient) -> Self {
+        Self {
+            people: Arc::new(SingleFlight::new(people)),
+            swapi_client,
+        }
+    }
+
+    /// When the people were last copied from Swapi, if they aren't retrieved from Swapi directly.
+    pub async fn synced_at(&self) -> Option<DateTime<Utc>> {
+        self.people.synced_at().await
+    }
+
+    /// How well the cache in front of the people performs, if there is one.
+    pub fn cache_stats(&self) -> Option<CacheStats> {
+        self.people.cache_stats()
+    }
+
+    /// State of the circuit breaker in front of Swapi, if people are searched there.
+    pub fn circuit_state(&self) -> Option<CircuitState> {
+        self.people.circuit_state()
     }
 
     /// Is Yoda taller than the person with the given name?
-    #[instrument(skip(self), fields(height))]
-    pub async fn is_taller_than(&self, name: &str) -> Result<YodaTallerOutcome, YodaTallerError> {
-        let yoda_height = 66;
-        let characters = self
-            .swapi_client
-            .people_by_name(name)
-            .await
-            .map_err(YodaTallerError::UnexpectedError)?;
-        let first_match = characters.get(0).ok_or(YodaTallerError::PersonNotFound)?;
-        let person_height = &first_match.height;
-        tracing::Span::current().record("height", person_height);
+    #[instrument(skip(self))]
+    pub async fn is_taller_than(
+        &self,
+        name: &str,
+        options: &LookupOptions,
+    ) -> Result<YodaTallerOutcome, YodaTallerError> {
+        let lookup = self
+            .is_taller_than_with(name, options, &Links::default(), None)
+            .await?;
+        Ok(lookup.outcome)
+    }
 
-        let other_height = person_height.parse::<u32>().map_err(|e| {
-            tracing::warn!("invalid height: {}", e);
-            YodaTallerError::HeightNotFound
-        })?;
-        let response = YodaTallerOutcome {
-            person: first_match.name.clone(),
-            taller: yoda_height > other_height,
+    /// Is Yoda taller than the person with the given name?
+    /// Also resolve the given links of the person and, if their height is unknown,
+    /// estimate it as requested.
+    #[instrument(skip(self))]
+    pub async fn is_taller_than_with(
+        &self,
+        name: &str,
+        options: &LookupOptions,
+        links: &Links,
+        estimate: Option<Estimate>,
+    ) -> Result<TallerLookup, YodaTallerError> {
+        let (person, stale_for) = self.found_person(name, options).await?;
+        let height = async {
+            match (person.height, estimate) {
+                (Some(height), _) => Ok((height, None)),
+                (None, Some(Estimate::Species)) => {
+                    let (height, basis) =
+                        estimate::species_height(&self.swapi_client, &person).await?;
+                    Ok((height, Some(basis)))
+                }
+                (None, None) => {
+                    tracing::warn!("unknown height");
+                    Err(YodaTallerError::HeightNotFound)
+                }
+            }
+        };
+        let expanded = async {
+            if links.is_empty() {
+                return Ok(None);
+            }
+            let expanded = Expanded::resolve(&self.swapi_client, &person, links).await?;
+            Ok(Some(expanded))
+        };
+        let ((height, basis), expanded) = tokio::try_join!(height, expanded)?;
+        let character = Character {
+            name: person.name,
+            height,
         };
-        Ok(response)
+        let mut outcome =
+            YodaTallerOutcome::from(Comparison::between(Character::yoda(), character));
+        outcome.estimated = basis.is_some();
+        outcome.basis = basis;
+        Ok(TallerLookup {
+            outcome,
+            expanded,
+            stale_for,
+        })
+    }
+
+    /// Is Yoda older than the person with the given name?
+    #[instrument(skip(self))]
+    pub async fn is_older_than(
+        &self,
+        name: &str,
+        options: &LookupOptions,
+    ) -> Result<YodaOlderOutcome, YodaTallerError> {
+        let person = self.person(name, options).await?;
+        let person_birth_year = person.birth_year.ok_or_else(|| {
+            tracing::warn!("unknown birth year");
+            YodaTallerError::BirthYearNotFound
+        })?;
+        let yoda_birth_year = yoda_birth_year();
+        Ok(YodaOlderOutcome {
+            person: person.name,
+            older: yoda_birth_year < person_birth_year,
+            yoda_birth_year,
+            person_birth_year,
+        })
+    }
+
+    /// Compare Yoda with the person with the given name by the given attribute.
+    #[instrument(skip(self))]
+    pub async fn compare_with_yoda(
+        &self,
+        name: &str,
+        attribute: Attribute,
+        options: &LookupOptions,
+    ) -> Result<AttributeComparison, YodaTallerError> {
+        let person = self.person(name, options).await?;
+        AttributeComparison::with_yoda(&person, attribute)
+            .inspect_err(|e| tracing::warn!("cannot compare: {e}"))
+    }
+
+    /// Rank Yoda and the people with the given names by height.
+    /// At most `concurrency` names are looked up at the same time.
+    /// People with the same height keep the order of the names, Yoda coming last among them.
+    #[instrument(skip(self))]
+    pub async fn rank_by_height(
+        &self,
+        names: &[String],
+        options: &LookupOptions,
+        concurrency: usize,
+    ) -> HeightRanking {
+        // Build the lookups before streaming them: the compiler can't prove that
+        // a stream mapping the borrowed names is `Send`, as axum handlers require.
+        let lookups: Vec<_> = names
+            .iter()
+            .map(|name| self.comparable_character(name, options))
+            .collect();
+        let lookups: Vec<_> = stream::iter(lookups)
+            .buffered(concurrency.max(1))
+            .collect()
+            .await;
+        let yoda = Character::yoda();
+        let mut characters = vec![];
+        let mut failures = vec![];
+        for lookup in lookups {
+            match lookup {
+                Ok(character) if character == yoda => {}
+                Ok(character) => characters.push(character),
+                Err(e) => failures.push(e),
+            }
+        }
+        characters.push(yoda.clone());
+        characters.sort_by_key(|character| Reverse(character.height));
+        let ranked = characters
+            .into_iter()
+            .enumerate()
+            .map(|(index, character)| RankedCharacter {
+                position: index + 1,
+                yoda: character == yoda,
+                character,
+            })
+            .collect();
+        HeightRanking { ranked, failures }
+    }
+
+    /// Compare the heights of the people with the given names with each other.
+    /// Each name is resolved only once, even if it is repeated,
+    /// and at most `concurrency` names are looked up at the same time.
+    #[instrument(skip(self))]
+    pub async fn height_matrix(
+        &self,
+        names: &[String],
+        options: &LookupOptions,
+        concurrency: usize,
+    ) -> HeightMatrix {
+        let mut distinct_names: Vec<&String> = vec![];
+        for name in names {
+            if !distinct_names.contains(&name) {
+                distinct_names.push(name);
+            }
+        }
+        // Built before streaming them, as in `rank_by_height`.
+        let lookups: Vec<_> = distinct_names
+            .iter()
+            .map(|name| self.comparable_character(name, options))
+            .collect();
+        let lookups: Vec<_> = stream::iter(lookups)
+            .buffered(concurrency.max(1))
+            .collect()
+            .await;
+        let mut characters = HashMap::new();
+        let mut failures = vec![];
+        for (name, lookup) in distinct_names.into_iter().zip(lookups) {
+            match lookup {
+                Ok(character) => {
+                    characters.insert(name, character);
+                }
+                Err(e) => failures.push(e),
+            }
+        }
+        let relations = names
+            .iter()
+            .map(|first| {
+                names
+                    .iter()
+                    .map(|second| {
+                        let (first, second) = (characters.get(first)?, characters.get(second)?);
+                        Some(Attribute::Height.relation(first.height.cmp(&second.height)))
+                    })
+                    .collect()
+            })
+            .collect();
+        HeightMatrix {
+            relations,
+            failures,
+        }
+    }
+
+    /// Compare the heights of the two people with the given names.
+    #[instrument(skip(self))]
+    pub async fn compare(
+        &self,
+        first: &str,
+        second: &str,
+        options: &LookupOptions,
+    ) -> Result<Comparison, ComparisonError> {
+        let (first, second) = tokio::try_join!(
+            self.comparable_character(first, options),
+            self.comparable_character(second, options)
+        )?;
+        Ok(Comparison::between(first, second))
+    }
+
+    async fn comparable_character(
+        &self,
+        name: &str,
+        options: &LookupOptions,
+    ) -> Result<Character, ComparisonError> {
+        self.character(name, options)
+            .await
+            .map_err(|error| ComparisonError {
+                query: name.to_string(),
+                error,
+            })
+    }
+
+    /// Look up the person that best matches the given name and parse their height.
+    #[instrument(skip(self), fields(height))]
+    pub async fn character(
+        &self,
+        name: &str,
+        options: &LookupOptions,
+    ) -> Result<Character, YodaTallerError> {
+        let person = self.person(name, options).await?;
+        let character = Character::try_from(person)?;
+        tracing::Span::current().record("height", character.height.cm());
+        Ok(character)
+    }
+
+    /// Which characters of the film with the given title is Yoda taller than?
+    #[instrument(skip(self))]
+    pub async fn shorter_than_yoda_in_film(
+        &self,
+        title: &str,
+    ) -> Result<FilmComparison, YodaTallerError> {
+        let film = self.film(title).await?;
+        let people = self.swapi_client.film_characters(&film).await?;
+        let yoda = Character::yoda();
+        let characters = people
+            .into_iter()
+            .filter_map(|person| {
+                let name = person.name.clone();
+                match Character::try_from(person) {
+                    Ok(character) => (character.height < yoda.height).then_some(Ok(character)),
+                    Err(error) => Some(Err(ComparisonError { query: name, error })),
+                }
+            })
+            .collect();
+        Ok(FilmComparison {
+            film: film.title,
+            characters,
+        })
+    }
+
+    /// Statistics about the heights of all the people of the people source.
+    #[instrument(skip(self))]
+    pub async fn height_stats(&self, bucket_width_cm: u32) -> Result<HeightStats, YodaTallerError> {
+        let people = self.people.all_people().await?;
+        Ok(HeightStats::compute(people, bucket_width_cm))
+    }
+
+    /// Look up the film whose title best matches the given one.
+    async fn film(&self, title: &str) -> Result<Film, YodaTallerError> {
+        let films = self.swapi_client.films_by_title(title).await?;
+        let mut best_matches = ranking::best_matches(title, films, MatchMode::Any);
+        if best_matches.len() > 1 {
+            let candidates = best_matches.iter().map(Candidate::from).collect();
+            return Err(YodaTallerError::AmbiguousFilm { candidates });
+        }
+        best_matches.pop().ok_or(YodaTallerError::FilmNotFound)
+    }
+
+    /// Look up the person that best matches the given name.
+    #[instrument(skip(self))]
+    pub async fn person(
+        &self,
+        name: &str,
+        options: &LookupOptions,
+    ) -> Result<Person, YodaTallerError> {
+        let (person, _) = self.found_person(name, options).await?;
+        Ok(person)
+    }
+
+    /// The person with the given name, and how long ago it was retrieved if it is stale.
+    async fn found_person(
+        &self,
+        name: &str,
+        options: &LookupOptions,
+    ) -> Result<(Person, Option<Duration>), YodaTallerError> {
+        let found = self.people.search(name).await?;
+        let best_matches = ranking::best_matches(name, found.people, options.match_mode);
+        if best_matches.len() > 1 && options.pick == Pick::Unique {
+            let candidates = best_matches.iter().map(Candidate::from).collect();
+            return Err(YodaTallerError::Ambiguous { candidates });
+        }
+        let person = best_matches
+            .into_iter()
+            .next()
+            .ok_or(YodaTallerError::PersonNotFound)?;
+        Ok((person, found.stale_for))
     }
 }
@@ -0,0 +1,211 @@
+use {
+    crate::helpers::{people, swapi_mock, test_app::TestApp},
+    reqwest::StatusCode,
+    std::time::Duration,
+    yoda_taller::{
+        height::Height,
+        server::taller_route::{BatchEntry, ErrorBody, YodaTallerResponse},
+        taller::{Relation, YodaTallerOutcome},
+    },
+};
+
+#[tokio::test]
+async fn batch_returns_one_result_per_name_in_order() {
+    let app = TestApp::spawn().await;
+    let (luke, arvel, yaddle) = (people::luke(), people::arvel(), people::yaddle());
+    for person in [&luke, &arvel, &yaddle] {
+        app.swapi_server
+            .mock_people_query(&person.name, swapi_mock::person_query_result(person))
+            .await;
+    }
+    let spock = "Spock";
+    app.swapi_server
+        .mock_people_query(spock, swapi_mock::empty_query_result())
+        .await;
+    let response = app
+        .send_batch_taller_req(&[&luke.name, &arvel.name, spock, &yaddle.name])
+        .await;
+    assert_eq!(StatusCode::OK, response.status());
+
+    let body: Vec<BatchEntry> = response.json().await.unwrap();
+    assert_eq!(
+        vec![
+            BatchEntry::Ok(Box::new(YodaTallerResponse {
+                query: luke.name.clone(),
+                result: YodaTallerOutcome {
+                    person: luke.name,
+                    taller: false,
+                    relation: Relation::Shorter,
+                    yoda_height: Height::from_cm(66),
+                    person_height: Height::from_cm(172),
+                    difference_cm: -106,
+                    estimated: false,
+                    basis: None
+                },
+                display: None,
+                expanded: None,
+                synced_at: None,
+                stale: false
+            })),
+            BatchEntry::Error(ErrorBody {
+                query: arvel.name,
+                error: "Person's height is unknown".to_string(),
+                candidates: vec![]
+            }),
+            BatchEntry::Error(ErrorBody {
+                query: spock.to_string(),
+                error: "Person not found".to_string(),
+                candidates: vec![]
+            }),
+            BatchEntry::Ok(Box::new(YodaTallerResponse {
+                query: yaddle.name.clone(),
+                result: YodaTallerOutcome {
+                    person: yaddle.name,
+                    taller: true,
+                    relation: Relation::Taller,
+                    yoda_height: Height::from_cm(66),
+                    person_height: Height::from_cm(61),
+                    difference_cm: 5,
+                    estimated: false,
+                    basis: None
+                },
+                display: None,
+                expanded: None,
+                synced_at: None,
+                stale: false
+            })),
+        ],
+        body
+    );
+}
+
+#[tokio::test]
+async fn empty_batch_returns_no_results() {
+    let app = TestApp::spawn().await;
+    let response = app.send_batch_taller_req(&[]).await;
+    assert_eq!(StatusCode::OK, response.status());
+
+    let body: Vec<BatchEntry> = response.json().await.unwrap();
+    assert!(body.is_empty());
+}
+
+#[tokio::test]
+async fn batch_returns_400_if_unknown_pick() {
+    let app = TestApp::spawn().await;
+    let response = app
+        .send_batch_taller_req_with_query(&["Luke", "Yoda"], &[("pick", "last")])
+        .await;
+
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+    let body: ErrorBody = response.json().await.unwrap();
+    assert_eq!("Luke,Yoda", body.query);
+    assert!(
+        body.error.contains("unknown variant `last`"),
+        "{}",
+        body.error
+    );
+}
+
+#[tokio::test]
+async fn batch_returns_400_if_body_is_not_json() {
+    let app = TestApp::spawn().await;
+    let response = app.send_batch_taller_raw_req("Luke,Yoda").await;
+
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+    let body: ErrorBody = response.json().await.unwrap();
+    assert_eq!("", body.query);
+    assert!(!body.error.is_empty());
+}
+
+#[tokio::test]
+async fn batch_returns_422_if_body_is_not_a_list_of_names() {
+    let app = TestApp::spawn().await;
+    let response = app.send_batch_taller_raw_req(r#"{"names": "Luke"}"#).await;
+
+    assert_eq!(StatusCode::UNPROCESSABLE_ENTITY, response.status());
+    let body: ErrorBody = response.json().await.unwrap();
+    assert_eq!("", body.query);
+    assert!(!body.error.is_empty());
+}
+
+#[tokio::test]
+async fn streamed_results_are_in_input_order_if_requested() {
+    let app = TestApp::spawn().await;
+    let (luke, yaddle) = (people::luke(), people::yaddle());
+    app.swapi_server
+        .mock_people_query_with_delay(
+            &luke.name,
+            swapi_mock::person_query_result(&luke),
+            Duration::from_millis(500),
+        )
+        .await;
+    app.swapi_server
+        .mock_people_query(&yaddle.name, swapi_mock::person_query_result(&yaddle))
+        .await;
+    let response = app
+        .send_stream_taller_req(&[&luke.name, &yaddle.name], "input")
+        .await;
+    assert_eq!(StatusCode::OK, response.status());
+    assert_eq!(
+        "application/x-ndjson",
+        response.headers()[reqwest::header::CONTENT_TYPE]
+    );
+
+    assert_eq!(
+        vec![luke.name, yaddle.name],
+        streamed_queries(response).await
+    );
+}
+
+#[tokio::test]
+async fn streamed_results_are_in_completion_order() {
+    let app = TestApp::spawn().await;
+    let (luke, yaddle) = (people::luke(), people::yaddle());
+    app.swapi_server
+        .mock_people_query_with_delay(
+            &luke.name,
+            swapi_mock::person_query_result(&luke),
+            Duration::from_millis(500),
+        )
+        .await;
+    app.swapi_server
+        .mock_people_query(&yaddle.name, swapi_mock::person_query_result(&yaddle))
+        .await;
+    let response = app
+        .send_stream_taller_req(&[&luke.name, &yaddle.name], "completion")
+        .await;
+    assert_eq!(StatusCode::OK, response.status());
+
+    assert_eq!(
+        vec![yaddle.name, luke.name],
+        streamed_queries(response).await
+    );
+}
+
+#[tokio::test]
+async fn stream_returns_400_if_unknown_order() {
+    let app = TestApp::spawn().await;
+    let response = app
+        .send_stream_taller_req(&["Luke", "Yoda"], "random")
+        .await;
+
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+    let body: ErrorBody = response.json().await.unwrap();
+    assert_eq!("Luke,Yoda", body.query);
+    assert!(
+        body.error.contains("unknown variant `random`"),
+        "{}",
+        body.error
+    );
+}
+
+/// Names sent by the user, in the order of the streamed results.
+async fn streamed_queries(response: reqwest::Response) -> Vec<String> {
+    let body = response.text().await.unwrap();
+    body.lines()
+        .map(|line| match serde_json::from_str(line).unwrap() {
+            BatchEntry::Ok(response) => response.query,
+            BatchEntry::Error(error) => error.query,
+        })
+        .collect()
+}
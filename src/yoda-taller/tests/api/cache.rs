@@ -0,0 +1,199 @@
+use {
+    crate::helpers::{people, swapi_mock, test_app::TestApp},
+    reqwest::{header, StatusCode},
+    std::time::Duration,
+    yoda_taller::{
+        server::{admin_route::CacheResponse, taller_route::YodaTallerResponse},
+        settings::Settings,
+        source::CacheStats,
+    },
+};
+
+fn enable_cache(settings: &mut Settings) {
+    settings.swapi.cache.enabled = true;
+}
+
+#[tokio::test]
+async fn same_name_is_searched_once() {
+    let app = TestApp::spawn_with(enable_cache).await;
+    let luke = people::luke();
+    let body = swapi_mock::person_query_result(&luke);
+    // Swapi is only called for the first search.
+    app.swapi_server.mock_people_query(&luke.name, body).await;
+
+    for name in [luke.name.as_str(), " LUKE skywalker"] {
+        let response = app.send_taller_req(name).await;
+        assert_eq!(StatusCode::OK, response.status());
+    }
+
+    let response = app.send_cache_stats_req().await;
+    assert_eq!(StatusCode::OK, response.status());
+    assert_eq!(
+        CacheResponse {
+            enabled: true,
+            stats: Some(CacheStats {
+                hits: 1,
+                misses: 1,
+                entries: 1
+            })
+        },
+        response.json().await.unwrap()
+    );
+}
+
+#[tokio::test]
+async fn people_not_found_are_cached_if_configured() {
+    let app = TestApp::spawn_with(|settings| {
+        enable_cache(settings);
+        settings.swapi.cache.cache_not_found = true;
+    })
+    .await;
+    let name = "Spock";
+    let body = swapi_mock::empty_query_result();
+    app.swapi_server.mock_people_query(name, body).await;
+
+    for _ in 0..2 {
+        let response = app.send_taller_req(name).await;
+        assert_eq!(StatusCode::NOT_FOUND, response.status());
+    }
+}
+
+#[tokio::test]
+async fn people_not_found_are_searched_again_by_default() {
+    let app = TestApp::spawn_with(enable_cache).await;
+    let name = "Spock";
+    let body = swapi_mock::empty_query_result();
+    app.swapi_server
+        .mock_people_query_times(name, body, 2)
+        .await;
+
+    for _ in 0..2 {
+        let response = app.send_taller_req(name).await;
+        assert_eq!(StatusCode::NOT_FOUND, response.status());
+    }
+}
+
+#[tokio::test]
+async fn expired_searches_reach_swapi_again() {
+    let app = TestApp::spawn_with(|settings| {
+        enable_cache(settings);
+        settings.swapi.cache.ttl_milliseconds = 0;
+    })
+    .await;
+    let luke = people::luke();
+    let body = swapi_mock::person_query_result(&luke);
+    app.swapi_server
+        .mock_people_query_times(&luke.name, body, 2)
+        .await;
+
+    for _ in 0..2 {
+        let response = app.send_taller_req(&luke.name).await;
+        assert_eq!(StatusCode::OK, response.status());
+    }
+}
+
+#[tokio::test]
+async fn oldest_search_is_evicted_when_full() {
+    let app = TestApp::spawn_with(|settings| {
+        enable_cache(settings);
+        settings.swapi.cache.max_entries = 1;
+    })
+    .await;
+    let luke = people::luke();
+    let yaddle = people::yaddle();
+    let body = swapi_mock::person_query_result(&luke);
+    app.swapi_server
+        .mock_people_query_times(&luke.name, body, 2)
+        .await;
+    let body = swapi_mock::person_query_result(&yaddle);
+    app.swapi_server.mock_people_query(&yaddle.name, body).await;
+
+    for person in [&luke, &yaddle, &luke] {
+        let response = app.send_taller_req(&person.name).await;
+        assert_eq!(StatusCode::OK, response.status());
+    }
+}
+
+#[tokio::test]
+async fn cache_is_disabled_by_default() {
+    let app = TestApp::spawn().await;
+    let response = app.send_cache_stats_req().await;
+    assert_eq!(StatusCode::OK, response.status());
+    assert_eq!(
+        CacheResponse {
+            enabled: false,
+            stats: None
+        },
+        response.json().await.unwrap()
+    );
+}
+
+/// Searches are always expired, and served stale when Swapi fails.
+fn serve_stale(settings: &mut Settings) {
+    enable_cache(settings);
+    settings.swapi.cache.ttl_milliseconds = 0;
+    settings.swapi.cache.serve_stale = true;
+}
+
+#[tokio::test]
+async fn expired_search_is_served_stale_if_swapi_fails() {
+    let app = TestApp::spawn_with(serve_stale).await;
+    let luke = people::luke();
+    let body = swapi_mock::person_query_result(&luke);
+    app.swapi_server.mock_people_query(&luke.name, body).await;
+    let response = app.send_taller_req(&luke.name).await;
+    assert_eq!(StatusCode::OK, response.status());
+    assert!(response.headers().get(header::WARNING).is_none());
+
+    app.swapi_server.reset().await;
+    app.swapi_server
+        .mock_people_query_failure(&luke.name, 1)
+        .await;
+    let response = app.send_taller_req(&luke.name).await;
+    assert_eq!(StatusCode::OK, response.status());
+    assert_eq!(
+        "110 - \"Response is Stale\"",
+        response.headers()[header::WARNING]
+    );
+    assert_eq!("0", response.headers()[header::AGE]);
+    let body: YodaTallerResponse = response.json().await.unwrap();
+    assert!(body.stale);
+    assert_eq!(luke.name, body.result.person);
+}
+
+#[tokio::test]
+async fn failures_are_not_hidden_without_stale_copy() {
+    let app = TestApp::spawn_with(serve_stale).await;
+    let name = "Luke Skywalker";
+    app.swapi_server.mock_people_query_failure(name, 1).await;
+    let response = app.send_taller_req(name).await;
+    assert_eq!(StatusCode::INTERNAL_SERVER_ERROR, response.status());
+}
+
+#[tokio::test]
+async fn stale_search_is_refreshed_in_background_once_swapi_recovers() {
+    let app = TestApp::spawn_with(serve_stale).await;
+    let luke = people::luke();
+    let body = swapi_mock::person_query_result(&luke);
+    app.swapi_server.mock_people_query(&luke.name, body).await;
+    app.send_taller_req(&luke.name).await;
+    app.swapi_server.reset().await;
+    app.swapi_server
+        .mock_people_query_failure(&luke.name, 1)
+        .await;
+    app.send_taller_req(&luke.name).await;
+
+    app.swapi_server.reset().await;
+    let body = swapi_mock::person_query_result(&luke);
+    // Searched by the background refresh, then by the last request.
+    app.swapi_server
+        .mock_people_query_times(&luke.name, body, 2)
+        .await;
+    // Served stale without waiting for Swapi, which failed last time.
+    let body: YodaTallerResponse = app.send_taller_req(&luke.name).await.json().await.unwrap();
+    assert!(body.stale);
+
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    let body: YodaTallerResponse = app.send_taller_req(&luke.name).await.json().await.unwrap();
+    assert!(!body.stale);
+}
@@ -0,0 +1,63 @@
+use {
+    crate::helpers::{people, swapi_mock, test_app::TestApp},
+    reqwest::StatusCode,
+    std::path::Path,
+    yoda_taller::{
+        height::Height,
+        server::taller_route::YodaTallerResponse,
+        swapi::{Cassette, CassetteMode},
+    },
+};
+
+async fn spawn_with_cassette(mode: CassetteMode, path: &Path) -> TestApp {
+    TestApp::spawn_with(|settings| {
+        settings.swapi.cassette = mode;
+        settings.swapi.cassette_path = Some(path.to_path_buf());
+    })
+    .await
+}
+
+#[tokio::test]
+async fn recorded_responses_are_replayed_without_swapi() {
+    let cassettes = tempfile::tempdir().unwrap();
+    let cassette = cassettes.path().join("swapi.jsonl");
+    let luke = people::luke();
+
+    let recording_app = spawn_with_cassette(CassetteMode::Record, &cassette).await;
+    recording_app
+        .swapi_server
+        .mock_people_query(&luke.name, swapi_mock::person_query_result(&luke))
+        .await;
+    let response = recording_app.send_taller_req(&luke.name).await;
+    assert_eq!(StatusCode::OK, response.status());
+
+    // no Swapi mock is mounted on the server of the replaying app
+    let replaying_app = spawn_with_cassette(CassetteMode::Replay, &cassette).await;
+    let response = replaying_app.send_taller_req(&luke.name).await;
+    assert_eq!(StatusCode::OK, response.status());
+    let body: YodaTallerResponse = response.json().await.unwrap();
+    assert_eq!(luke.name, body.result.person);
+    assert_eq!(Height::from_cm(172), body.result.person_height);
+}
+
+#[tokio::test]
+async fn return_500_if_response_was_not_recorded() {
+    let cassettes = tempfile::tempdir().unwrap();
+    let cassette = cassettes.path().join("swapi.jsonl");
+    std::fs::File::create(&cassette).unwrap();
+
+    let app = spawn_with_cassette(CassetteMode::Replay, &cassette).await;
+    let response = app.send_taller_req("Luke Skywalker").await;
+    assert_eq!(StatusCode::INTERNAL_SERVER_ERROR, response.status());
+}
+
+#[test]
+fn cassette_with_invalid_status_is_rejected() {
+    let cassettes = tempfile::tempdir().unwrap();
+    let cassette = cassettes.path().join("swapi.jsonl");
+    let line = r#"{"request":"GET /api/people/?search=Luke","status":1000,"body":""}"#;
+    std::fs::write(&cassette, line).unwrap();
+
+    let err = Cassette::load(&cassette).err().unwrap();
+    assert!(err.to_string().contains("invalid status 1000"), "{err}");
+}
@@ -0,0 +1,85 @@
+use {
+    crate::helpers::{people, swapi_mock, test_app::TestApp},
+    reqwest::{header, StatusCode},
+    std::time::Duration,
+    yoda_taller::{
+        server::{startup::HealthResponse, taller_route::ErrorBody},
+        settings::Settings,
+        source::CircuitState,
+    },
+};
+
+const OPEN_DURATION: Duration = Duration::from_millis(300);
+
+fn open_after_two_failures(settings: &mut Settings) {
+    settings.swapi.circuit_breaker.failure_threshold = 2;
+    settings.swapi.circuit_breaker.open_milliseconds = OPEN_DURATION.as_millis() as u64;
+}
+
+async fn circuit_state(app: &TestApp) -> Option<CircuitState> {
+    let response = app.send_health_check_req().await;
+    let health: HealthResponse = response.json().await.unwrap();
+    health.swapi_circuit
+}
+
+#[tokio::test]
+async fn swapi_is_not_called_once_the_circuit_is_open() {
+    let app = TestApp::spawn_with(open_after_two_failures).await;
+    let name = "Luke Skywalker";
+    app.swapi_server.mock_people_query_failure(name, 2).await;
+
+    for _ in 0..2 {
+        let response = app.send_taller_req(name).await;
+        assert_eq!(StatusCode::INTERNAL_SERVER_ERROR, response.status());
+    }
+    assert_eq!(Some(CircuitState::Open), circuit_state(&app).await);
+
+    let response = app.send_taller_req(name).await;
+    assert_eq!(StatusCode::SERVICE_UNAVAILABLE, response.status());
+    assert_eq!("1", response.headers()[header::RETRY_AFTER]);
+    assert_eq!(
+        ErrorBody {
+            query: name.to_string(),
+            error: "Swapi is unavailable".to_string(),
+            candidates: vec![]
+        },
+        response.json().await.unwrap()
+    );
+}
+
+#[tokio::test]
+async fn circuit_closes_once_swapi_recovers() {
+    let app = TestApp::spawn_with(open_after_two_failures).await;
+    let luke = people::luke();
+    app.swapi_server
+        .mock_people_query_failure(&luke.name, 2)
+        .await;
+    for _ in 0..2 {
+        app.send_taller_req(&luke.name).await;
+    }
+
+    app.swapi_server.reset().await;
+    let body = swapi_mock::person_query_result(&luke);
+    app.swapi_server.mock_people_query(&luke.name, body).await;
+    tokio::time::sleep(OPEN_DURATION).await;
+
+    let response = app.send_taller_req(&luke.name).await;
+    assert_eq!(StatusCode::OK, response.status());
+    assert_eq!(Some(CircuitState::Closed), circuit_state(&app).await);
+}
+
+#[tokio::test]
+async fn people_not_found_do_not_open_the_circuit() {
+    let app = TestApp::spawn_with(open_after_two_failures).await;
+    let name = "Spock";
+    let body = swapi_mock::empty_query_result();
+    app.swapi_server
+        .mock_people_query_times(name, body, 3)
+        .await;
+
+    for _ in 0..3 {
+        let response = app.send_taller_req(name).await;
+        assert_eq!(StatusCode::NOT_FOUND, response.status());
+    }
+    assert_eq!(Some(CircuitState::Closed), circuit_state(&app).await);
+}
@@ -0,0 +1,199 @@
+use {
+    crate::helpers::{people, swapi_mock, test_app::TestApp},
+    reqwest::StatusCode,
+    yoda_taller::{
+        attribute::{Attribute, AttributeComparison, AttributeValue},
+        height::Height,
+        server::taller_route::{AttributeComparisonResponse, CompareResponse, ErrorBody},
+        swapi::Mass,
+        taller::{Character, Comparison, LookupOptions, Relation, YodaTallerError},
+    },
+};
+
+#[tokio::test]
+async fn luke_is_taller_than_yaddle() {
+    let app = TestApp::spawn().await;
+    let luke = people::luke();
+    let yaddle = people::yaddle();
+    for person in [&luke, &yaddle] {
+        let body = swapi_mock::person_query_result(person);
+        app.swapi_server.mock_people_query(&person.name, body).await;
+    }
+    let comparison = app
+        .yoda_taller
+        .compare(&luke.name, &yaddle.name, &LookupOptions::default())
+        .await
+        .unwrap();
+    assert_eq!(
+        Comparison {
+            first: Character {
+                name: luke.name,
+                height: Height::from_cm(172)
+            },
+            second: Character {
+                name: yaddle.name,
+                height: Height::from_cm(61)
+            },
+            relation: Relation::Taller
+        },
+        comparison
+    );
+}
+
+#[tokio::test]
+async fn cannot_compare_with_person_with_invalid_height() {
+    let app = TestApp::spawn().await;
+    let luke = people::luke();
+    let arvel = people::arvel();
+    for person in [&luke, &arvel] {
+        let body = swapi_mock::person_query_result(person);
+        app.swapi_server.mock_people_query(&person.name, body).await;
+    }
+    let err = app
+        .yoda_taller
+        .compare(&luke.name, &arvel.name, &LookupOptions::default())
+        .await
+        .unwrap_err();
+    assert_eq!(arvel.name, err.query);
+    assert!(matches!(err.error, YodaTallerError::HeightNotFound));
+}
+
+#[tokio::test]
+async fn yaddle_is_shorter_than_luke() {
+    let app = TestApp::spawn().await;
+    let luke = people::luke();
+    let yaddle = people::yaddle();
+    for person in [&luke, &yaddle] {
+        let body = swapi_mock::person_query_result(person);
+        app.swapi_server.mock_people_query(&person.name, body).await;
+    }
+    let response = app.send_compare_req(&yaddle.name, &luke.name).await;
+    assert_eq!(StatusCode::OK, response.status());
+
+    let body = response.json().await.unwrap();
+    assert_eq!(
+        CompareResponse {
+            queries: [yaddle.name.clone(), luke.name.clone()],
+            result: Comparison {
+                first: Character {
+                    name: yaddle.name,
+                    height: Height::from_cm(61)
+                },
+                second: Character {
+                    name: luke.name,
+                    height: Height::from_cm(172)
+                },
+                relation: Relation::Shorter
+            },
+            synced_at: None
+        },
+        body
+    );
+}
+
+#[tokio::test]
+async fn return_404_if_spock() {
+    let app = TestApp::spawn().await;
+    let yoda = people::yoda();
+    let name = "Spock";
+    let body = swapi_mock::person_query_result(&yoda);
+    app.swapi_server.mock_people_query(&yoda.name, body).await;
+    let body = swapi_mock::empty_query_result();
+    app.swapi_server.mock_people_query(name, body).await;
+
+    let response = app.send_compare_req(&yoda.name, name).await;
+    assert_eq!(StatusCode::NOT_FOUND, response.status());
+    assert_eq!(
+        ErrorBody {
+            query: name.to_string(),
+            error: "Person not found".to_string(),
+            candidates: vec![]
+        },
+        response.json().await.unwrap()
+    );
+}
+
+#[tokio::test]
+async fn return_400_if_unknown_match_mode() {
+    let app = TestApp::spawn().await;
+    let response = app
+        .send_compare_req_with_query("Yoda", "Luke", &[("match", "fuzzy")])
+        .await;
+
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+    let body: ErrorBody = response.json().await.unwrap();
+    assert_eq!("Yoda,Luke", body.query);
+    assert!(
+        body.error.contains("unknown variant `fuzzy`"),
+        "{}",
+        body.error
+    );
+}
+
+#[tokio::test]
+async fn yoda_is_lighter_than_luke() {
+    let app = TestApp::spawn().await;
+    let luke = people::luke();
+    let body = swapi_mock::person_query_result(&luke);
+    app.swapi_server.mock_people_query(&luke.name, body).await;
+    let comparison = app
+        .yoda_taller
+        .compare_with_yoda(&luke.name, Attribute::Mass, &LookupOptions::default())
+        .await
+        .unwrap();
+    assert_eq!(
+        AttributeComparison {
+            person: luke.name,
+            attribute: Attribute::Mass,
+            relation: Relation::Lighter,
+            yoda_value: AttributeValue::Mass(Mass::from_kg(17.0)),
+            person_value: AttributeValue::Mass(Mass::from_kg(77.0)),
+        },
+        comparison
+    );
+}
+
+#[tokio::test]
+async fn yoda_is_older_than_anakin() {
+    let app = TestApp::spawn().await;
+    let anakin = people::anakin();
+    let body = swapi_mock::person_query_result(&anakin);
+    app.swapi_server.mock_people_query(&anakin.name, body).await;
+    let response = app.send_compare_by_req(&anakin.name, "birth_year").await;
+    assert_eq!(StatusCode::OK, response.status());
+
+    let body = response.json().await.unwrap();
+    assert_eq!(
+        AttributeComparisonResponse {
+            query: anakin.name.clone(),
+            result: AttributeComparison {
+                person: anakin.name,
+                attribute: Attribute::BirthYear,
+                relation: Relation::Older,
+                yoda_value: AttributeValue::BirthYear("896BBY".parse().unwrap()),
+                person_value: AttributeValue::BirthYear("41.9BBY".parse().unwrap()),
+            },
+            synced_at: None
+        },
+        body
+    );
+}
+
+#[tokio::test]
+async fn return_404_if_unknown_mass() {
+    let app = TestApp::spawn().await;
+    let yaddle = people::yaddle();
+    let body = swapi_mock::person_query_result(&yaddle);
+    app.swapi_server.mock_people_query(&yaddle.name, body).await;
+    let response = app.send_compare_by_req(&yaddle.name, "mass").await;
+
+    assert_eq!(StatusCode::NOT_FOUND, response.status());
+    assert_eq!(
+        ErrorBody {
+            query: yaddle.name,
+            error: "Person's mass is unknown".to_string(),
+            candidates: vec![]
+        },
+        response.json().await.unwrap()
+    );
+}
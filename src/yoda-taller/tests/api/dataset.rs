@@ -0,0 +1,91 @@
+use {
+    crate::helpers::{people, swapi_mock, test_app::TestApp},
+    reqwest::StatusCode,
+    std::io::Write,
+    tempfile::NamedTempFile,
+    yoda_taller::{
+        height::Height,
+        server::taller_route::YodaTallerResponse,
+        settings::SwapiMode,
+        source::{Dataset, PeopleSource},
+        swapi::Person,
+    },
+};
+
+/// A dump file of the given Swapi json.
+fn dump_file(json: serde_json::Value) -> NamedTempFile {
+    let mut file = NamedTempFile::new().unwrap();
+    serde_json::to_writer(&mut file, &json).unwrap();
+    file.flush().unwrap();
+    file
+}
+
+#[tokio::test]
+async fn taller_works_without_swapi_in_file_mode() {
+    let (luke, yaddle) = (people::luke(), people::yaddle());
+    let dump = dump_file(serde_json::to_value([&luke, &yaddle]).unwrap());
+    let app = TestApp::spawn_with(|settings| {
+        settings.swapi.mode = SwapiMode::File;
+        settings.swapi.dataset_path = Some(dump.path().to_path_buf());
+    })
+    .await;
+
+    // no Swapi mock is mounted
+    let response = app.send_taller_req("yaddle").await;
+    assert_eq!(StatusCode::OK, response.status());
+    let body: YodaTallerResponse = response.json().await.unwrap();
+    assert_eq!(yaddle.name, body.result.person);
+    assert_eq!(Height::from_cm(61), body.result.person_height);
+}
+
+#[tokio::test]
+async fn height_stats_work_without_swapi_in_file_mode() {
+    let (luke, yaddle) = (people::luke(), people::yaddle());
+    let dump = dump_file(serde_json::to_value([&luke, &yaddle]).unwrap());
+    let app = TestApp::spawn_with(|settings| {
+        settings.swapi.mode = SwapiMode::File;
+        settings.swapi.dataset_path = Some(dump.path().to_path_buf());
+    })
+    .await;
+
+    // no Swapi mock is mounted
+    let stats = app.wait_for_height_stats().await;
+    assert_eq!(2, stats.count);
+}
+
+#[tokio::test]
+async fn dataset_is_searched_like_swapi() {
+    let (luke, anakin, yaddle) = (people::luke(), people::anakin(), people::yaddle());
+    // a page of results, as returned by Swapi
+    let dump = dump_file(swapi_mock::people_query_page_result(
+        &[&luke, &anakin, &yaddle],
+        3,
+        None,
+    ));
+    let dataset = Dataset::load(dump.path()).unwrap();
+
+    let people: Vec<Person> = dataset.people_by_name("SKY").await.unwrap();
+    assert_eq!(vec![luke, anakin], people);
+    assert!(dataset.people_by_name("spock").await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn padded_name_searched_first_is_cached_for_every_spelling_in_file_mode() {
+    let yaddle = people::yaddle();
+    let dump = dump_file(serde_json::to_value([&yaddle]).unwrap());
+    let app = TestApp::spawn_with(|settings| {
+        settings.swapi.mode = SwapiMode::File;
+        settings.swapi.dataset_path = Some(dump.path().to_path_buf());
+        settings.swapi.cache.enabled = true;
+        settings.swapi.cache.cache_not_found = true;
+    })
+    .await;
+
+    // The first search fills the cache entry shared by both names.
+    for name in [" yaddle ", "Yaddle"] {
+        let response = app.send_taller_req(name).await;
+        assert_eq!(StatusCode::OK, response.status());
+        let body: YodaTallerResponse = response.json().await.unwrap();
+        assert_eq!(yaddle.name, body.result.person);
+    }
+}
@@ -0,0 +1,104 @@
+use {
+    crate::helpers::{people, resources, swapi_mock, test_app::TestApp},
+    reqwest::StatusCode,
+    yoda_taller::{
+        height::Height,
+        server::taller_route::{ErrorBody, FilmCharacterEntry, ShorterThanYodaResponse},
+        taller::{Candidate, Character},
+    },
+};
+
+#[tokio::test]
+async fn yoda_is_taller_than_some_characters_of_a_film() {
+    let app = TestApp::spawn().await;
+    let (yoda, yaddle, arvel, luke) = (
+        people::yoda(),
+        people::yaddle(),
+        people::arvel(),
+        people::luke(),
+    );
+    let film = resources::empire_strikes_back(&[&yoda, &yaddle, &arvel, &luke]);
+    let title = "empire";
+    app.swapi_server
+        .mock_films_query(title, swapi_mock::film_query_result(&film))
+        .await;
+    for person in [&yoda, &yaddle, &arvel, &luke] {
+        app.swapi_server
+            .mock_resource(&person.url, serde_json::to_value(person).unwrap())
+            .await;
+    }
+    let response = app.send_shorter_than_yoda_req(title).await;
+    assert_eq!(StatusCode::OK, response.status());
+
+    assert_eq!(
+        ShorterThanYodaResponse {
+            query: title.to_string(),
+            film: film.title,
+            yoda_height: Height::from_cm(66),
+            characters: vec![
+                FilmCharacterEntry::Shorter(Character {
+                    name: yaddle.name,
+                    height: Height::from_cm(61)
+                }),
+                FilmCharacterEntry::Error(ErrorBody {
+                    query: arvel.name,
+                    error: "Person's height is unknown".to_string(),
+                    candidates: vec![]
+                }),
+            ]
+        },
+        response.json().await.unwrap()
+    );
+}
+
+#[tokio::test]
+async fn return_404_if_film_not_found() {
+    let app = TestApp::spawn().await;
+    let title = "Star Trek";
+    app.swapi_server
+        .mock_films_query(title, swapi_mock::empty_query_result())
+        .await;
+    let response = app.send_shorter_than_yoda_req(title).await;
+
+    assert_eq!(StatusCode::NOT_FOUND, response.status());
+    assert_eq!(
+        ErrorBody {
+            query: title.to_string(),
+            error: "Film not found".to_string(),
+            candidates: vec![]
+        },
+        response.json().await.unwrap()
+    );
+}
+
+#[tokio::test]
+async fn return_300_if_several_films_match() {
+    let app = TestApp::spawn().await;
+    let yoda = people::yoda();
+    let empire = resources::empire_strikes_back(&[&yoda]);
+    let jedi = resources::return_of_the_jedi(&[&yoda]);
+    let title = "e";
+    app.swapi_server
+        .mock_films_query(title, swapi_mock::films_query_result(&[&empire, &jedi]))
+        .await;
+    let response = app.send_shorter_than_yoda_req(title).await;
+    assert_eq!(StatusCode::MULTIPLE_CHOICES, response.status());
+
+    assert_eq!(
+        ErrorBody {
+            query: title.to_string(),
+            error: "Multiple films match the title".to_string(),
+            candidates: vec![
+                Candidate {
+                    id: Some(2),
+                    name: empire.title
+                },
+                Candidate {
+                    id: Some(3),
+                    name: jedi.title
+                },
+            ]
+        },
+        response.json().await.unwrap()
+    );
+}
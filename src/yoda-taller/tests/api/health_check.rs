@@ -1,4 +1,8 @@
-use {crate::helpers::test_app::TestApp, reqwest::StatusCode};
+use {
+    crate::helpers::test_app::TestApp,
+    reqwest::StatusCode,
+    yoda_taller::{server::startup::HealthResponse, source::CircuitState},
+};
 
 #[tokio::test]
 async fn health_check_works() {
@@ -9,12 +13,17 @@ async fn health_check_works() {
     // Act
     let response = client
         // Use the returned application address
-        .get(&format!("{}/health_check", &app.server_address()))
+        .get(format!("{}/health_check", &app.server_address()))
         .send()
         .await
         .expect("Failed to execute request.");
 
     // Assert
     assert_eq!(StatusCode::OK, response.status());
-    assert_eq!(Some(0), response.content_length());
+    assert_eq!(
+        HealthResponse {
+            swapi_circuit: Some(CircuitState::Closed)
+        },
+        response.json().await.unwrap()
+    );
 }
@@ -1,4 +1,5 @@
 pub mod people;
+pub mod resources;
 pub mod swapi_mock;
 pub mod test_app;
 mod test_traces;
@@ -1,12 +1,29 @@
 //! Some characters used for testings.
 
-use yoda_taller::swapi::Person;
+use {
+    super::swapi_mock::SWAPI_PUBLIC_URL,
+    yoda_taller::{height::Height, swapi::Person},
+};
 
 /// Yoda himself.
 pub fn yoda() -> Person {
     Person {
         name: "Yoda".to_string(),
-        height: "66".to_string(),
+        height: Some(Height::from_cm(66)),
+        mass: Some("17".parse().unwrap()),
+        hair_color: Some("white".to_string()),
+        skin_color: Some("green".to_string()),
+        eye_color: Some("brown".to_string()),
+        birth_year: Some("896BBY".parse().unwrap()),
+        gender: Some("male".to_string()),
+        homeworld: swapi_url("planets", 28),
+        films: swapi_urls("films", &[2, 3, 4, 5, 6]),
+        species: swapi_urls("species", &[6]),
+        vehicles: vec![],
+        starships: vec![],
+        created: "2014-12-15T12:26:01.042000Z".parse().unwrap(),
+        edited: "2014-12-20T21:17:50.345000Z".parse().unwrap(),
+        url: swapi_url("people", 20),
     }
 }
 
@@ -14,7 +31,43 @@ pub fn yoda() -> Person {
 pub fn luke() -> Person {
     Person {
         name: "Luke Skywalker".to_string(),
-        height: "172".to_string(),
+        height: Some(Height::from_cm(172)),
+        mass: Some("77".parse().unwrap()),
+        hair_color: Some("blond".to_string()),
+        skin_color: Some("fair".to_string()),
+        eye_color: Some("blue".to_string()),
+        birth_year: Some("19BBY".parse().unwrap()),
+        gender: Some("male".to_string()),
+        homeworld: swapi_url("planets", 1),
+        films: swapi_urls("films", &[1, 2, 3, 6]),
+        species: vec![],
+        vehicles: swapi_urls("vehicles", &[14, 30]),
+        starships: swapi_urls("starships", &[12, 22]),
+        created: "2014-12-09T13:50:51.644000Z".parse().unwrap(),
+        edited: "2014-12-20T21:17:56.891000Z".parse().unwrap(),
+        url: swapi_url("people", 1),
+    }
+}
+
+/// Another character taller than Yoda, with the same surname as Luke.
+pub fn anakin() -> Person {
+    Person {
+        name: "Anakin Skywalker".to_string(),
+        height: Some(Height::from_cm(188)),
+        mass: Some("84".parse().unwrap()),
+        hair_color: Some("blond".to_string()),
+        skin_color: Some("fair".to_string()),
+        eye_color: Some("blue".to_string()),
+        birth_year: Some("41.9BBY".parse().unwrap()),
+        gender: Some("male".to_string()),
+        homeworld: swapi_url("planets", 1),
+        films: swapi_urls("films", &[4, 5, 6]),
+        species: vec![],
+        vehicles: swapi_urls("vehicles", &[44, 46]),
+        starships: swapi_urls("starships", &[39, 59, 65]),
+        created: "2014-12-10T16:20:44.310000Z".parse().unwrap(),
+        edited: "2014-12-20T21:17:50.327000Z".parse().unwrap(),
+        url: swapi_url("people", 11),
     }
 }
 
@@ -22,7 +75,43 @@ pub fn luke() -> Person {
 pub fn yaddle() -> Person {
     Person {
         name: "Yaddle".to_string(),
-        height: "61".to_string(),
+        height: Some(Height::from_cm(61)),
+        mass: None,
+        hair_color: Some("brown".to_string()),
+        skin_color: Some("green".to_string()),
+        eye_color: Some("brown".to_string()),
+        birth_year: Some("509BBY".parse().unwrap()),
+        gender: Some("female".to_string()),
+        homeworld: swapi_url("planets", 28),
+        films: swapi_urls("films", &[4]),
+        species: swapi_urls("species", &[6]),
+        vehicles: vec![],
+        starships: vec![],
+        created: "2014-12-20T12:26:01.042000Z".parse().unwrap(),
+        edited: "2014-12-20T21:17:50.345000Z".parse().unwrap(),
+        url: swapi_url("people", 83),
+    }
+}
+
+/// A character whose name contains "Vader", without starting with it.
+pub fn vader() -> Person {
+    Person {
+        name: "Darth Vader".to_string(),
+        height: Some(Height::from_cm(202)),
+        mass: Some("136".parse().unwrap()),
+        hair_color: Some("none".to_string()),
+        skin_color: Some("white".to_string()),
+        eye_color: Some("yellow".to_string()),
+        birth_year: Some("41.9BBY".parse().unwrap()),
+        gender: Some("male".to_string()),
+        homeworld: swapi_url("planets", 1),
+        films: swapi_urls("films", &[1, 2, 3, 6]),
+        species: vec![],
+        vehicles: vec![],
+        starships: swapi_urls("starships", &[13]),
+        created: "2014-12-10T15:18:20.704000Z".parse().unwrap(),
+        edited: "2014-12-20T21:17:50.313000Z".parse().unwrap(),
+        url: swapi_url("people", 4),
     }
 }
 
@@ -30,6 +119,28 @@ pub fn yaddle() -> Person {
 pub fn arvel() -> Person {
     Person {
         name: "Arvel Crynyd".to_string(),
-        height: "unknown".to_string(),
+        height: None,
+        mass: None,
+        hair_color: Some("brown".to_string()),
+        skin_color: Some("fair".to_string()),
+        eye_color: Some("brown".to_string()),
+        birth_year: None,
+        gender: Some("male".to_string()),
+        homeworld: swapi_url("planets", 28),
+        films: swapi_urls("films", &[3]),
+        species: swapi_urls("species", &[1]),
+        vehicles: vec![],
+        starships: swapi_urls("starships", &[28]),
+        created: "2014-12-18T11:16:33.020000Z".parse().unwrap(),
+        edited: "2014-12-20T21:17:50.369000Z".parse().unwrap(),
+        url: swapi_url("people", 28),
     }
 }
+
+pub fn swapi_url(resource: &str, id: u32) -> String {
+    format!("{SWAPI_PUBLIC_URL}/api/{resource}/{id}/")
+}
+
+pub fn swapi_urls(resource: &str, ids: &[u32]) -> Vec<String> {
+    ids.iter().map(|id| swapi_url(resource, *id)).collect()
+}
@@ -0,0 +1,110 @@
+//! Some resources linked from the characters used for testing.
+
+use {
+    super::people::{swapi_url, swapi_urls},
+    yoda_taller::{
+        height::Height,
+        swapi::{Film, Person, Planet, Species},
+    },
+};
+
+/// Yoda's homeworld, which Swapi doesn't know much about.
+pub fn yodas_homeworld() -> Planet {
+    Planet {
+        name: "unknown".to_string(),
+        rotation_period: None,
+        orbital_period: None,
+        diameter: None,
+        climate: "unknown".to_string(),
+        gravity: "unknown".to_string(),
+        terrain: "unknown".to_string(),
+        surface_water: None,
+        population: None,
+        residents: swapi_urls("people", &[20, 23, 29, 32, 74]),
+        films: vec![],
+        created: "2014-12-15T12:25:59.569000Z".parse().unwrap(),
+        edited: "2014-12-20T20:58:18.466000Z".parse().unwrap(),
+        url: swapi_url("planets", 28),
+    }
+}
+
+/// Yoda's species.
+pub fn yodas_species() -> Species {
+    Species {
+        name: "Yoda's species".to_string(),
+        classification: "mammal".to_string(),
+        designation: "sentient".to_string(),
+        average_height: Some(Height::from_cm(66)),
+        skin_colors: "green, yellow".to_string(),
+        hair_colors: "brown, white".to_string(),
+        eye_colors: "brown, green, yellow".to_string(),
+        average_lifespan: "900".to_string(),
+        homeworld: Some(swapi_url("planets", 28)),
+        language: "Galactic basic".to_string(),
+        people: swapi_urls("people", &[20]),
+        films: swapi_urls("films", &[2, 3, 4, 5, 6]),
+        created: "2014-12-15T12:27:22.877000Z".parse().unwrap(),
+        edited: "2014-12-20T21:36:42.148000Z".parse().unwrap(),
+        url: swapi_url("species", 6),
+    }
+}
+
+/// Species of Luke, Anakin and many others.
+pub fn human() -> Species {
+    Species {
+        name: "Human".to_string(),
+        classification: "mammal".to_string(),
+        designation: "sentient".to_string(),
+        average_height: Some(Height::from_cm(180)),
+        skin_colors: "caucasian, black, asian, hispanic".to_string(),
+        hair_colors: "blonde, brown, black, red".to_string(),
+        eye_colors: "brown, blue, green, hazel, grey, amber".to_string(),
+        average_lifespan: "120".to_string(),
+        homeworld: Some(swapi_url("planets", 9)),
+        language: "Galactic Basic".to_string(),
+        people: swapi_urls("people", &[66, 67, 68, 74]),
+        films: swapi_urls("films", &[1, 2, 3, 4, 5, 6]),
+        created: "2014-12-10T13:52:11.567000Z".parse().unwrap(),
+        edited: "2014-12-20T21:36:42.136000Z".parse().unwrap(),
+        url: swapi_url("species", 1),
+    }
+}
+
+/// A film with the given characters.
+pub fn empire_strikes_back(characters: &[&Person]) -> Film {
+    Film {
+        title: "The Empire Strikes Back".to_string(),
+        episode_id: 5,
+        opening_crawl: "It is a dark time for the Rebellion.".to_string(),
+        director: "Irvin Kershner".to_string(),
+        producer: "Gary Kurtz, Rick McCallum".to_string(),
+        release_date: "1980-05-17".parse().unwrap(),
+        characters: characters.iter().map(|person| person.url.clone()).collect(),
+        planets: swapi_urls("planets", &[4, 5, 6, 27]),
+        starships: swapi_urls("starships", &[3, 10, 11, 12]),
+        vehicles: swapi_urls("vehicles", &[8, 14, 16, 18]),
+        species: swapi_urls("species", &[1, 2, 3, 6, 7]),
+        created: "2014-12-12T11:26:24.656000Z".parse().unwrap(),
+        edited: "2014-12-15T13:07:53.386000Z".parse().unwrap(),
+        url: swapi_url("films", 2),
+    }
+}
+
+pub fn return_of_the_jedi(characters: &[&Person]) -> Film {
+    Film {
+        title: "Return of the Jedi".to_string(),
+        episode_id: 6,
+        opening_crawl: "Luke Skywalker has returned to his home planet of Tatooine.".to_string(),
+        director: "Richard Marquand".to_string(),
+        producer: "Howard G. Kazanjian, George Lucas, Rick McCallum".to_string(),
+        release_date: "1983-05-25".parse().unwrap(),
+        characters: characters.iter().map(|person| person.url.clone()).collect(),
+        planets: swapi_urls("planets", &[1, 5, 7, 8, 9]),
+        starships: swapi_urls("starships", &[2, 3, 10, 11, 12]),
+        vehicles: swapi_urls("vehicles", &[8, 16, 18, 19]),
+        species: swapi_urls("species", &[1, 2, 3, 5, 6]),
+        created: "2014-12-18T10:39:33.255000Z".parse().unwrap(),
+        edited: "2014-12-20T09:48:37.462000Z".parse().unwrap(),
+        url: swapi_url("films", 3),
+    }
+}
@@ -1,12 +1,15 @@
 use {
     std::{ops::Deref, time::Duration},
     wiremock::{
-        matchers::{method, path, query_param},
-        Mock, MockServer, ResponseTemplate,
+        matchers::{method, path, query_param, query_param_is_missing},
+        Match, Mock, MockServer, Request, ResponseTemplate,
     },
-    yoda_taller::swapi::Person,
+    yoda_taller::swapi::{Film, Person},
 };
 
+/// Host name Swapi builds its links from, which differs from the mock server address.
+pub const SWAPI_PUBLIC_URL: &str = "http://127.0.1.1:9992";
+
 pub struct SwapiMock {
     server: MockServer,
 }
@@ -29,7 +32,22 @@ impl SwapiMock {
 
 impl SwapiMock {
     pub async fn mock_people_query(&self, search: &str, body: serde_json::Value) {
-        self.mock_people_query_response(search, ResponseTemplate::new(200).set_body_json(body))
+        self.mock_people_query_times(search, body, 1).await
+    }
+
+    /// Mock a people query that must be received `times` times.
+    pub async fn mock_people_query_times(&self, search: &str, body: serde_json::Value, times: u64) {
+        self.mock_people_query_response(
+            search,
+            ResponseTemplate::new(200).set_body_json(body),
+            times,
+        )
+        .await
+    }
+
+    /// Mock a people query failing `times` times, as if Swapi was down.
+    pub async fn mock_people_query_failure(&self, search: &str, times: u64) {
+        self.mock_people_query_response(search, ResponseTemplate::new(503), times)
             .await
     }
 
@@ -44,55 +62,146 @@ impl SwapiMock {
             ResponseTemplate::new(200)
                 .set_body_json(body)
                 .set_delay(delay),
+            1,
         )
         .await
     }
 
-    async fn mock_people_query_response(&self, search: &str, response: ResponseTemplate) {
+    /// Mock the given page of a people query.
+    /// Pages after the first one are requested through the `next` link.
+    pub async fn mock_people_query_page(&self, search: &str, page: u32, body: serde_json::Value) {
         Mock::given(method("GET"))
             .and(path("/api/people/"))
+            .and(PeopleSearch::new(search))
+            .and(query_param("page", page.to_string()))
+            .respond_with(ResponseTemplate::new(200).set_body_json(body))
+            .named("mock people query page")
+            .expect(1)
+            .mount(&self.server)
+            .await;
+    }
+
+    pub async fn mock_films_query(&self, search: &str, body: serde_json::Value) {
+        Mock::given(method("GET"))
+            .and(path("/api/films/"))
             .and(query_param("search", search))
+            .respond_with(ResponseTemplate::new(200).set_body_json(body))
+            .named("mock films query")
+            .expect(1)
+            .mount(&self.server)
+            .await;
+    }
+
+    /// Mock the resource linked by the given Swapi url, such as a person's homeworld.
+    pub async fn mock_resource(&self, url: &str, body: serde_json::Value) {
+        let api_start = url.find("/api/").unwrap();
+        Mock::given(method("GET"))
+            .and(path(&url[api_start..]))
+            .respond_with(ResponseTemplate::new(200).set_body_json(body))
+            .named("mock resource")
+            .expect(1)
+            .mount(&self.server)
+            .await;
+    }
+
+    /// Mock the given page of the list of all people.
+    /// The list is requested again at every refresh of the statistics.
+    pub async fn mock_all_people_page(&self, page: u32, body: serde_json::Value) {
+        let mock = Mock::given(method("GET"))
+            .and(path("/api/people/"))
+            .and(query_param_is_missing("search"));
+        let mock = if page == 1 {
+            mock.and(query_param_is_missing("page"))
+        } else {
+            mock.and(query_param("page", page.to_string()))
+        };
+        mock.respond_with(ResponseTemplate::new(200).set_body_json(body))
+            .named("mock all people page")
+            .mount(&self.server)
+            .await;
+    }
+
+    async fn mock_people_query_response(
+        &self,
+        search: &str,
+        response: ResponseTemplate,
+        times: u64,
+    ) {
+        Mock::given(method("GET"))
+            .and(path("/api/people/"))
+            .and(PeopleSearch::new(search))
+            .and(query_param_is_missing("page"))
             .respond_with(response)
             .named("mock people query")
-            .expect(1)
+            .expect(times)
             .mount(&self.server)
             .await;
     }
 }
 
+/// Matches a search of people by the given name.
+/// Like Swapi, it ignores the case of the name.
+struct PeopleSearch(String);
+
+impl PeopleSearch {
+    fn new(search: &str) -> Self {
+        Self(search.to_lowercase())
+    }
+}
+
+impl Match for PeopleSearch {
+    fn matches(&self, request: &Request) -> bool {
+        request
+            .url
+            .query_pairs()
+            .any(|(key, value)| key == "search" && value.to_lowercase() == self.0)
+    }
+}
+
+/// Url of the given page of the list of all people, as returned by Swapi in the `next` field.
+/// Swapi builds it from its own host name, not from the one it is reached with.
+pub fn all_people_page_url(page: u32) -> String {
+    format!("{SWAPI_PUBLIC_URL}/api/people/?page={page}")
+}
+
+/// Url of the given page of a people query, as returned by Swapi in the `next` field.
+pub fn people_query_page_url(search: &str, page: u32) -> String {
+    format!("{SWAPI_PUBLIC_URL}/api/people/?search={search}&page={page}")
+}
+
 pub fn person_query_result(person: &Person) -> serde_json::Value {
+    people_query_page_result(&[person], 1, None)
+}
+
+/// A page of a people query, linking to the `next` page if there is one.
+pub fn people_query_page_result(
+    people: &[&Person],
+    count: usize,
+    next: Option<String>,
+) -> serde_json::Value {
+    let results: Vec<serde_json::Value> = people.iter().map(|p| person_json(p)).collect();
+    serde_json::json!( {
+        "count": count,
+        "next": next,
+        "previous": null,
+        "results": results
+    })
+}
+
+fn person_json(person: &Person) -> serde_json::Value {
+    serde_json::to_value(person).unwrap()
+}
+
+pub fn film_query_result(film: &Film) -> serde_json::Value {
+    films_query_result(&[film])
+}
+
+pub fn films_query_result(films: &[&Film]) -> serde_json::Value {
     serde_json::json!( {
-        "count": 1,
+        "count": films.len(),
         "next": null,
         "previous": null,
-        "results": [
-            {
-                "birth_year": "896BBY",
-                "created": "2014-12-15T12:26:01.042000Z",
-                "edited": "2014-12-20T21:17:50.345000Z",
-                "eye_color": "brown",
-                "films": [
-                    "http://127.0.1.1:9992/api/films/2/",
-                    "http://127.0.1.1:9992/api/films/3/",
-                    "http://127.0.1.1:9992/api/films/4/",
-                    "http://127.0.1.1:9992/api/films/5/",
-                    "http://127.0.1.1:9992/api/films/6/"
-                ],
-                "gender": "male",
-                "hair_color": "white",
-                "height": person.height,
-                "homeworld": "http://127.0.1.1:9992/api/planets/28/",
-                "mass": "17",
-                "name": person.name,
-                "skin_color": "green",
-                "species": [
-                    "http://127.0.1.1:9992/api/species/6/"
-                ],
-                "starships": [],
-                "url": "http://127.0.1.1:9992/api/people/20/",
-                "vehicles": []
-            }
-        ]
+        "results": films
     })
 }
 
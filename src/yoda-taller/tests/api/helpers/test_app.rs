@@ -1,15 +1,24 @@
 use {
     super::{swapi_mock::SwapiMock, test_traces::init_test_traces},
+    reqwest::StatusCode,
     std::time::Duration,
     yoda_taller::{
         server::startup::Application,
-        settings::{ApplicationSettings, Settings, SwapiSettings},
-        swapi::SwapiClient,
+        settings::{
+            ApplicationSettings, CacheSettings, CircuitBreakerSettings, Settings, StatsSettings,
+            SwapiMode, SwapiSettings,
+        },
+        stats::HeightStats,
+        swapi::{CassetteMode, SwapiClient},
         taller::YodaTaller,
     },
 };
 
 pub const SWAPI_TIMEOUT: Duration = Duration::from_secs(5);
+pub const SWAPI_MAX_PAGES: usize = 3;
+pub const BATCH_CONCURRENCY: usize = 2;
+pub const MAX_RANK_NAMES: usize = 5;
+pub const MAX_MATRIX_NAMES: usize = 4;
 
 pub struct TestApp {
     pub port: u16,
@@ -21,16 +30,36 @@ pub struct TestApp {
 
 impl TestApp {
     pub async fn spawn() -> Self {
+        Self::spawn_with(|_| {}).await
+    }
+
+    /// Spawn the app with the default test settings, changed by `configure`.
+    pub async fn spawn_with(configure: impl FnOnce(&mut Settings)) -> Self {
         init_test_traces();
         let swapi_server = SwapiMock::start().await;
 
-        let settings = Settings {
-            application: ApplicationSettings { port: 0 },
+        let mut settings = Settings {
+            application: ApplicationSettings {
+                port: 0,
+                batch_concurrency: BATCH_CONCURRENCY,
+                max_rank_names: MAX_RANK_NAMES,
+                max_matrix_names: MAX_MATRIX_NAMES,
+            },
             swapi: SwapiSettings {
                 base_url: swapi_server.uri(),
                 timeout_milliseconds: SWAPI_TIMEOUT.as_millis().try_into().unwrap(),
+                max_pages: SWAPI_MAX_PAGES,
+                mode: SwapiMode::Http,
+                dataset_path: None,
+                database_path: None,
+                cassette: CassetteMode::Passthrough,
+                cassette_path: None,
+                cache: CacheSettings::default(),
+                circuit_breaker: CircuitBreakerSettings::default(),
             },
+            stats: StatsSettings::default(),
         };
+        configure(&mut settings);
         let yoda_taller = settings.swapi.yoda_taller().unwrap();
         let swapi_client = settings.swapi.swapi_client().unwrap();
         let application_bind = Application::bind(settings).unwrap();
@@ -52,9 +81,189 @@ impl TestApp {
         format!("http://localhost:{}", self.port)
     }
 
+    pub async fn send_health_check_req(&self) -> reqwest::Response {
+        self.api_client
+            .get(format!("{}/health_check", &self.server_address()))
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
     pub async fn send_taller_req(&self, name: &str) -> reqwest::Response {
+        self.send_taller_req_with_query(name, &[]).await
+    }
+
+    pub async fn send_taller_req_with_query(
+        &self,
+        name: &str,
+        query: &[(&str, &str)],
+    ) -> reqwest::Response {
+        self.api_client
+            .get(format!("{}/taller/{name}", &self.server_address()))
+            .query(query)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn send_batch_taller_req(&self, names: &[&str]) -> reqwest::Response {
+        self.send_batch_taller_req_with_query(names, &[]).await
+    }
+
+    pub async fn send_batch_taller_req_with_query(
+        &self,
+        names: &[&str],
+        query: &[(&str, &str)],
+    ) -> reqwest::Response {
+        self.api_client
+            .post(format!("{}/taller", &self.server_address()))
+            .query(query)
+            .json(names)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    /// Send a batch request with the given raw body, which may not be valid JSON.
+    pub async fn send_batch_taller_raw_req(&self, body: &str) -> reqwest::Response {
+        self.api_client
+            .post(format!("{}/taller", &self.server_address()))
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body.to_string())
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn send_stream_taller_req(&self, names: &[&str], order: &str) -> reqwest::Response {
+        self.api_client
+            .post(format!("{}/taller/stream", &self.server_address()))
+            .query(&[("order", order)])
+            .body(names.join("\n"))
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn send_height_stats_req(&self) -> reqwest::Response {
+        self.api_client
+            .get(format!("{}/stats/heights", &self.server_address()))
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    /// Wait for the background refresh to compute the height statistics.
+    pub async fn wait_for_height_stats(&self) -> HeightStats {
+        for _ in 0..50 {
+            let response = self.send_height_stats_req().await;
+            if response.status() == StatusCode::OK {
+                return response.json().await.unwrap();
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+        panic!("height statistics were never computed");
+    }
+
+    pub async fn send_rank_req(&self, names: &str) -> reqwest::Response {
+        self.send_rank_req_with_query(names, &[]).await
+    }
+
+    pub async fn send_rank_req_with_query(
+        &self,
+        names: &str,
+        query: &[(&str, &str)],
+    ) -> reqwest::Response {
+        self.api_client
+            .get(format!("{}/rank", &self.server_address()))
+            .query(&[("names", names)])
+            .query(query)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn send_matrix_req(&self, names: &[&str]) -> reqwest::Response {
+        self.send_matrix_req_with_query(names, &[]).await
+    }
+
+    pub async fn send_matrix_req_with_query(
+        &self,
+        names: &[&str],
+        query: &[(&str, &str)],
+    ) -> reqwest::Response {
+        self.api_client
+            .post(format!("{}/matrix", &self.server_address()))
+            .query(query)
+            .json(names)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    /// Send a matrix request with the given raw body, which may not be valid JSON.
+    pub async fn send_matrix_raw_req(&self, body: &str) -> reqwest::Response {
+        self.api_client
+            .post(format!("{}/matrix", &self.server_address()))
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body.to_string())
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn send_cache_stats_req(&self) -> reqwest::Response {
+        self.api_client
+            .get(format!("{}/admin/cache", &self.server_address()))
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn send_older_req(&self, name: &str) -> reqwest::Response {
+        self.api_client
+            .get(format!("{}/older/{name}", &self.server_address()))
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn send_compare_by_req(&self, name: &str, attribute: &str) -> reqwest::Response {
+        self.api_client
+            .get(format!("{}/compare/{name}", &self.server_address()))
+            .query(&[("by", attribute)])
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn send_shorter_than_yoda_req(&self, title: &str) -> reqwest::Response {
+        self.api_client
+            .get(format!(
+                "{}/films/{title}/shorter-than-yoda",
+                &self.server_address()
+            ))
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn send_compare_req(&self, first: &str, second: &str) -> reqwest::Response {
+        self.send_compare_req_with_query(first, second, &[]).await
+    }
+
+    pub async fn send_compare_req_with_query(
+        &self,
+        first: &str,
+        second: &str,
+        query: &[(&str, &str)],
+    ) -> reqwest::Response {
         self.api_client
-            .get(&format!("{}/taller/{name}", &self.server_address()))
+            .get(format!(
+                "{}/compare/{first}/{second}",
+                &self.server_address()
+            ))
+            .query(query)
             .send()
             .await
             .expect("Failed to execute request.")
@@ -1,5 +1,19 @@
+mod batch;
+mod cache;
+mod cassette;
+mod circuit_breaker;
+mod compare;
+mod dataset;
+mod films;
 mod health_check;
 mod helpers;
+mod matrix;
+mod older;
 mod person;
+mod rank;
+mod single_flight;
+mod source;
+mod sqlite;
+mod stats;
 mod taller;
 mod taller_server;
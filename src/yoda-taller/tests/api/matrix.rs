@@ -0,0 +1,103 @@
+use {
+    crate::helpers::{
+        people, swapi_mock,
+        test_app::{TestApp, MAX_MATRIX_NAMES},
+    },
+    reqwest::StatusCode,
+    yoda_taller::{
+        server::taller_route::{ErrorBody, MatrixResponse},
+        taller::Relation,
+    },
+};
+
+#[tokio::test]
+async fn matrix_compares_each_person_with_each_other() {
+    let app = TestApp::spawn().await;
+    let (luke, yaddle) = (people::luke(), people::yaddle());
+    // each name is looked up only once, even if repeated
+    for person in [&luke, &yaddle] {
+        app.swapi_server
+            .mock_people_query(&person.name, swapi_mock::person_query_result(person))
+            .await;
+    }
+    let spock = "Spock";
+    app.swapi_server
+        .mock_people_query(spock, swapi_mock::empty_query_result())
+        .await;
+    let names = [luke.name.as_str(), &yaddle.name, spock, &luke.name];
+    let response = app.send_matrix_req(&names).await;
+    assert_eq!(StatusCode::OK, response.status());
+
+    let (taller, shorter, equal) = (
+        Some(Relation::Taller),
+        Some(Relation::Shorter),
+        Some(Relation::Equal),
+    );
+    assert_eq!(
+        MatrixResponse {
+            queries: names.iter().map(|name| name.to_string()).collect(),
+            relations: vec![
+                vec![equal, taller, None, equal],
+                vec![shorter, equal, None, shorter],
+                vec![None, None, None, None],
+                vec![equal, taller, None, equal],
+            ],
+            errors: vec![ErrorBody {
+                query: spock.to_string(),
+                error: "Person not found".to_string(),
+                candidates: vec![]
+            }],
+            synced_at: None,
+        },
+        response.json().await.unwrap()
+    );
+}
+
+#[tokio::test]
+async fn return_400_if_unknown_pick() {
+    let app = TestApp::spawn().await;
+    let response = app
+        .send_matrix_req_with_query(&["Luke", "Yoda"], &[("pick", "last")])
+        .await;
+
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+    let body: ErrorBody = response.json().await.unwrap();
+    assert_eq!("Luke,Yoda", body.query);
+    assert!(
+        body.error.contains("unknown variant `last`"),
+        "{}",
+        body.error
+    );
+}
+
+#[tokio::test]
+async fn return_400_if_too_many_names() {
+    let app = TestApp::spawn().await;
+    let names: Vec<String> = (0..=MAX_MATRIX_NAMES)
+        .map(|i| format!("Clone {i}"))
+        .collect();
+    let names: Vec<&str> = names.iter().map(String::as_str).collect();
+    let response = app.send_matrix_req(&names).await;
+
+    // no Swapi mock is mounted, as no name is looked up
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+    assert_eq!(
+        ErrorBody {
+            query: names.join(","),
+            error: format!("Too many names, at most {MAX_MATRIX_NAMES} are allowed"),
+            candidates: vec![]
+        },
+        response.json().await.unwrap()
+    );
+}
+
+#[tokio::test]
+async fn return_400_if_body_is_not_json() {
+    let app = TestApp::spawn().await;
+    let response = app.send_matrix_raw_req("Luke,Yoda").await;
+
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+    let body: ErrorBody = response.json().await.unwrap();
+    assert_eq!("", body.query);
+    assert!(!body.error.is_empty());
+}
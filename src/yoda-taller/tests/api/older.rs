@@ -0,0 +1,116 @@
+use {
+    crate::helpers::{people, swapi_mock, test_app::TestApp},
+    reqwest::StatusCode,
+    std::cmp::Ordering,
+    yoda_taller::{
+        birth_year::BirthYear,
+        server::taller_route::{ErrorBody, YodaOlderResponse},
+        taller::{yoda_birth_year, LookupOptions, YodaOlderOutcome, YodaTallerError},
+    },
+};
+
+#[tokio::test]
+async fn yoda_is_older_than_luke() {
+    let app = TestApp::spawn().await;
+    let luke = people::luke();
+    let body = swapi_mock::person_query_result(&luke);
+    app.swapi_server.mock_people_query(&luke.name, body).await;
+    let is_yoda_older = app
+        .yoda_taller
+        .is_older_than(&luke.name, &LookupOptions::default())
+        .await
+        .unwrap();
+    assert_eq!(
+        YodaOlderOutcome {
+            person: luke.name,
+            older: true,
+            yoda_birth_year: yoda_birth_year(),
+            person_birth_year: "19BBY".parse().unwrap(),
+        },
+        is_yoda_older
+    );
+}
+
+#[tokio::test]
+async fn yoda_is_not_older_than_himself() {
+    let app = TestApp::spawn().await;
+    let yoda = people::yoda();
+    let body = swapi_mock::person_query_result(&yoda);
+    app.swapi_server.mock_people_query(&yoda.name, body).await;
+    let is_yoda_older = app
+        .yoda_taller
+        .is_older_than(&yoda.name, &LookupOptions::default())
+        .await
+        .unwrap();
+    assert!(!is_yoda_older.older);
+}
+
+#[tokio::test]
+async fn cannot_compare_yoda_and_person_with_unknown_birth_year() {
+    let app = TestApp::spawn().await;
+    let arvel = people::arvel();
+    let body = swapi_mock::person_query_result(&arvel);
+    app.swapi_server.mock_people_query(&arvel.name, body).await;
+    let is_older_err = app
+        .yoda_taller
+        .is_older_than(&arvel.name, &LookupOptions::default())
+        .await
+        .unwrap_err();
+    assert!(matches!(is_older_err, YodaTallerError::BirthYearNotFound));
+}
+
+#[tokio::test]
+async fn older_route_returns_both_birth_years() {
+    let app = TestApp::spawn().await;
+    let anakin = people::anakin();
+    let query_body = swapi_mock::person_query_result(&anakin);
+    app.swapi_server
+        .mock_people_query(&anakin.name, query_body)
+        .await;
+    let response = app.send_older_req(&anakin.name).await;
+    assert_eq!(StatusCode::OK, response.status());
+
+    let body = response.json().await.unwrap();
+    assert_eq!(
+        YodaOlderResponse {
+            query: anakin.name.clone(),
+            result: YodaOlderOutcome {
+                person: anakin.name,
+                older: true,
+                yoda_birth_year: yoda_birth_year(),
+                person_birth_year: "41.9BBY".parse().unwrap(),
+            },
+            synced_at: None
+        },
+        body
+    );
+}
+
+#[tokio::test]
+async fn return_404_if_unknown_birth_year() {
+    let app = TestApp::spawn().await;
+    let arvel = people::arvel();
+    let query_body = swapi_mock::person_query_result(&arvel);
+    app.swapi_server
+        .mock_people_query(&arvel.name, query_body)
+        .await;
+    let response = app.send_older_req(&arvel.name).await;
+
+    assert_eq!(StatusCode::NOT_FOUND, response.status());
+    assert_eq!(
+        ErrorBody {
+            query: arvel.name,
+            error: "Person's birth year is unknown".to_string(),
+            candidates: vec![]
+        },
+        response.json().await.unwrap()
+    );
+}
+
+#[test]
+fn years_of_the_battle_are_equal_in_both_eras() {
+    let before: BirthYear = "0BBY".parse().unwrap();
+    let after: BirthYear = "0ABY".parse().unwrap();
+    assert_eq!(before, after);
+    assert_eq!(Some(Ordering::Equal), before.partial_cmp(&after));
+}
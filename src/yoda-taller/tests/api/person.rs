@@ -3,7 +3,12 @@ use {
         people, swapi_mock,
         test_app::{TestApp, SWAPI_TIMEOUT},
     },
+    chrono::SecondsFormat,
     std::time::Duration,
+    yoda_taller::{
+        height::Height,
+        swapi::{Mass, SwapiClient},
+    },
 };
 
 // Call swapi to assert how tall Luke is.
@@ -15,7 +20,11 @@ async fn luke_is_tall() {
     app.swapi_server
         .mock_people_query(&luke.name, response_body)
         .await;
-    let people = app.swapi_client.people_by_name(&luke.name).await.unwrap();
+    let people = app
+        .swapi_client
+        .people_page_by_name(&luke.name)
+        .await
+        .unwrap();
     assert_eq!(people, vec![luke]);
 }
 
@@ -28,7 +37,7 @@ async fn spock_is_not_found() {
     app.swapi_server
         .mock_people_query(name, response_body)
         .await;
-    let people = app.swapi_client.people_by_name(name).await.unwrap();
+    let people = app.swapi_client.people_page_by_name(name).await.unwrap();
     assert!(people.is_empty());
 }
 
@@ -43,8 +52,111 @@ async fn swapi_client_returns_timeout_error_if_timeout() {
         .await;
     let err = app
         .swapi_client
-        .people_by_name(&luke.name)
+        .people_page_by_name(&luke.name)
         .await
         .unwrap_err();
     assert!(err.is_timeout());
 }
+
+// Swapi returns at most 10 people per page.
+#[tokio::test]
+async fn all_pages_are_retrieved() {
+    let app = TestApp::spawn().await;
+    let (luke, yaddle, yoda) = (people::luke(), people::yaddle(), people::yoda());
+    let search = "a";
+    let swapi = &app.swapi_server;
+    let first_page = swapi_mock::people_query_page_result(
+        &[&luke],
+        3,
+        Some(swapi_mock::people_query_page_url(search, 2)),
+    );
+    swapi.mock_people_query(search, first_page).await;
+    let second_page = swapi_mock::people_query_page_result(
+        &[&yaddle],
+        3,
+        Some(swapi_mock::people_query_page_url(search, 3)),
+    );
+    swapi.mock_people_query_page(search, 2, second_page).await;
+    let third_page = swapi_mock::people_query_page_result(&[&yoda], 3, None);
+    swapi.mock_people_query_page(search, 3, third_page).await;
+
+    let people = app.swapi_client.all_people_by_name(search).await.unwrap();
+    assert_eq!(people, vec![luke, yaddle, yoda]);
+}
+
+#[tokio::test]
+async fn pages_over_the_limit_are_not_retrieved() {
+    let app = TestApp::spawn().await;
+    let luke = people::luke();
+    let search = "a";
+    let first_page = swapi_mock::people_query_page_result(
+        &[&luke],
+        2,
+        Some(swapi_mock::people_query_page_url(search, 2)),
+    );
+    app.swapi_server.mock_people_query(search, first_page).await;
+
+    let swapi_client = SwapiClient::new(app.swapi_server.uri(), SWAPI_TIMEOUT, 1).unwrap();
+    let people = swapi_client.all_people_by_name(search).await.unwrap();
+    assert_eq!(people, vec![luke]);
+}
+
+#[tokio::test]
+async fn first_page_is_retrieved_without_page_limit() {
+    let app = TestApp::spawn().await;
+    let luke = people::luke();
+    let search = "a";
+    let first_page = swapi_mock::people_query_page_result(
+        &[&luke],
+        2,
+        Some(swapi_mock::people_query_page_url(search, 2)),
+    );
+    app.swapi_server.mock_people_query(search, first_page).await;
+
+    let swapi_client = SwapiClient::new(app.swapi_server.uri(), SWAPI_TIMEOUT, 0).unwrap();
+    let people = swapi_client.all_people_by_name(search).await.unwrap();
+    assert_eq!(people, vec![luke]);
+}
+
+#[tokio::test]
+async fn unknown_and_formatted_values_are_parsed() {
+    let app = TestApp::spawn().await;
+    let name = "Jabba";
+    let body = serde_json::json!({
+        "count": 1,
+        "next": null,
+        "previous": null,
+        "results": [
+            {
+                "name": "Jabba Desilijic Tiure",
+                "height": "175",
+                "mass": "1,358",
+                "hair_color": "n/a",
+                "skin_color": "green-tan, brown",
+                "eye_color": "orange",
+                "birth_year": "600BBY",
+                "gender": "hermaphrodite",
+                "homeworld": "http://127.0.1.1:9992/api/planets/24/",
+                "films": ["http://127.0.1.1:9992/api/films/1/"],
+                "species": ["http://127.0.1.1:9992/api/species/5/"],
+                "vehicles": [],
+                "starships": [],
+                "created": "2014-12-10T17:11:31.638000Z",
+                "edited": "2014-12-20T21:17:50.338000Z",
+                "url": "http://127.0.1.1:9992/api/people/16/"
+            }
+        ]
+    });
+    app.swapi_server.mock_people_query(name, body).await;
+    let people = app.swapi_client.people_page_by_name(name).await.unwrap();
+    let jabba = &people[0];
+    assert_eq!(Some(Height::from_cm(175)), jabba.height);
+    assert_eq!(Some(Mass::from_kg(1358.0)), jabba.mass);
+    assert_eq!(None, jabba.hair_color);
+    assert_eq!(Some("green-tan, brown"), jabba.skin_color.as_deref());
+    assert_eq!(
+        "2014-12-10T17:11:31.638Z",
+        jabba.created.to_rfc3339_opts(SecondsFormat::Millis, true)
+    );
+    assert_eq!(Some(16), jabba.id());
+}
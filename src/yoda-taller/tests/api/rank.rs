@@ -0,0 +1,127 @@
+use {
+    crate::helpers::{
+        people, swapi_mock,
+        test_app::{TestApp, MAX_RANK_NAMES},
+    },
+    reqwest::StatusCode,
+    yoda_taller::{
+        height::Height,
+        server::taller_route::{ErrorBody, RankResponse},
+        taller::{Character, RankedCharacter},
+    },
+};
+
+#[tokio::test]
+async fn people_are_ranked_by_height_with_yoda() {
+    let app = TestApp::spawn().await;
+    let (luke, yaddle, arvel) = (people::luke(), people::yaddle(), people::arvel());
+    for person in [&luke, &yaddle, &arvel] {
+        app.swapi_server
+            .mock_people_query(&person.name, swapi_mock::person_query_result(person))
+            .await;
+    }
+    let spock = "Spock";
+    app.swapi_server
+        .mock_people_query(spock, swapi_mock::empty_query_result())
+        .await;
+    let names = [yaddle.name.as_str(), &arvel.name, spock, &luke.name].join(",");
+    let response = app.send_rank_req(&names).await;
+    assert_eq!(StatusCode::OK, response.status());
+
+    assert_eq!(
+        RankResponse {
+            ranked: vec![
+                RankedCharacter {
+                    position: 1,
+                    character: Character {
+                        name: luke.name,
+                        height: Height::from_cm(172)
+                    },
+                    yoda: false
+                },
+                RankedCharacter {
+                    position: 2,
+                    character: Character::yoda(),
+                    yoda: true
+                },
+                RankedCharacter {
+                    position: 3,
+                    character: Character {
+                        name: yaddle.name,
+                        height: Height::from_cm(61)
+                    },
+                    yoda: false
+                },
+            ],
+            yoda_position: 2,
+            unranked: vec![ErrorBody {
+                query: arvel.name,
+                error: "Person's height is unknown".to_string(),
+                candidates: vec![]
+            }],
+            errors: vec![ErrorBody {
+                query: spock.to_string(),
+                error: "Person not found".to_string(),
+                candidates: vec![]
+            }],
+            synced_at: None,
+        },
+        response.json().await.unwrap()
+    );
+}
+
+#[tokio::test]
+async fn yoda_is_ranked_once() {
+    let app = TestApp::spawn().await;
+    let yoda = people::yoda();
+    app.swapi_server
+        .mock_people_query(&yoda.name, swapi_mock::person_query_result(&yoda))
+        .await;
+    let response = app.send_rank_req(&yoda.name).await;
+    assert_eq!(StatusCode::OK, response.status());
+
+    let body: RankResponse = response.json().await.unwrap();
+    assert_eq!(
+        vec![RankedCharacter {
+            position: 1,
+            character: Character::yoda(),
+            yoda: true
+        }],
+        body.ranked
+    );
+}
+
+#[tokio::test]
+async fn return_400_if_unknown_match_mode() {
+    let app = TestApp::spawn().await;
+    let response = app
+        .send_rank_req_with_query("Luke,Yoda", &[("match", "fuzzy")])
+        .await;
+
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+    let body: ErrorBody = response.json().await.unwrap();
+    assert!(
+        body.error.contains("unknown variant `fuzzy`"),
+        "{}",
+        body.error
+    );
+}
+
+#[tokio::test]
+async fn return_400_if_too_many_names() {
+    let app = TestApp::spawn().await;
+    let names: Vec<String> = (0..=MAX_RANK_NAMES).map(|i| format!("Clone {i}")).collect();
+    let names = names.join(",");
+    let response = app.send_rank_req(&names).await;
+
+    // no Swapi mock is mounted, as no name is looked up
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+    assert_eq!(
+        ErrorBody {
+            query: names,
+            error: format!("Too many names, at most {MAX_RANK_NAMES} are allowed"),
+            candidates: vec![]
+        },
+        response.json().await.unwrap()
+    );
+}
@@ -0,0 +1,53 @@
+use {
+    crate::helpers::{people, swapi_mock, test_app::TestApp},
+    futures::future::join_all,
+    reqwest::StatusCode,
+    std::time::Duration,
+    yoda_taller::taller::{LookupOptions, YodaTallerError},
+};
+
+const SEARCH_DELAY: Duration = Duration::from_millis(500);
+
+#[tokio::test]
+async fn concurrent_searches_of_the_same_name_reach_swapi_once() {
+    let app = TestApp::spawn().await;
+    let luke = people::luke();
+    let body = swapi_mock::person_query_result(&luke);
+    // Swapi is slow, so that all the searches are in flight at the same time.
+    app.swapi_server
+        .mock_people_query_with_delay(&luke.name, body, SEARCH_DELAY)
+        .await;
+
+    // The padded name starts the search, which the other names join.
+    let first = app.send_taller_req(" LUKE SKYWALKER ");
+    let others = async {
+        tokio::time::sleep(SEARCH_DELAY / 5).await;
+        let names = [luke.name.as_str(), "luke skywalker"];
+        join_all(names.map(|name| app.send_taller_req(name))).await
+    };
+    let (first, others) = tokio::join!(first, others);
+    for response in std::iter::once(first).chain(others) {
+        assert_eq!(StatusCode::OK, response.status());
+    }
+}
+
+#[tokio::test]
+async fn errors_are_shared_by_concurrent_searches() {
+    let app = TestApp::spawn().await;
+    let name = "Luke Skywalker";
+    let body = serde_json::json!( {
+        "invalid": "response"
+    });
+    app.swapi_server
+        .mock_people_query_with_delay(name, body, SEARCH_DELAY)
+        .await;
+
+    let options = LookupOptions::default();
+    let searches = (0..3).map(|_| app.yoda_taller.is_taller_than(name, &options));
+    for result in join_all(searches).await {
+        match result.unwrap_err() {
+            YodaTallerError::UnexpectedError(e) => assert!(e.is_decode()),
+            _ => panic!("unexpected error"),
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use {
+    crate::helpers::{people, test_app::TestApp},
+    async_trait::async_trait,
+    std::sync::Arc,
+    yoda_taller::{
+        height::Height,
+        source::PeopleSource,
+        swapi::Person,
+        taller::{LookupOptions, YodaTaller, YodaTallerError},
+    },
+};
+
+/// People known in advance, without calling Swapi.
+struct KnownPeople(Vec<Person>);
+
+#[async_trait]
+impl PeopleSource for KnownPeople {
+    async fn people_by_name(&self, name: &str) -> Result<Vec<Person>, YodaTallerError> {
+        let name = name.to_lowercase();
+        let people = self
+            .0
+            .iter()
+            .filter(|person| person.name.to_lowercase().contains(&name))
+            .cloned()
+            .collect();
+        Ok(people)
+    }
+
+    async fn all_people(&self) -> Result<Vec<Person>, YodaTallerError> {
+        Ok(self.0.clone())
+    }
+}
+
+#[tokio::test]
+async fn people_are_looked_up_in_the_given_source() {
+    let app = TestApp::spawn().await;
+    let source = KnownPeople(vec![people::luke(), people::yaddle()]);
+    let yoda_taller = YodaTaller::new(Arc::new(source), app.swapi_client.clone());
+
+    let outcome = yoda_taller
+        .is_taller_than("yaddle", &LookupOptions::default())
+        .await
+        .unwrap();
+    assert!(outcome.taller);
+    assert_eq!(Height::from_cm(61), outcome.person_height);
+}
+
+#[tokio::test]
+async fn person_missing_from_the_given_source_is_not_found() {
+    let app = TestApp::spawn().await;
+    let source = KnownPeople(vec![people::luke()]);
+    let yoda_taller = YodaTaller::new(Arc::new(source), app.swapi_client.clone());
+
+    let err = yoda_taller
+        .is_taller_than("yaddle", &LookupOptions::default())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, YodaTallerError::PersonNotFound));
+}
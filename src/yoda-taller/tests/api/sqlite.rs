@@ -0,0 +1,126 @@
+use {
+    crate::helpers::{people, swapi_mock, test_app::TestApp},
+    chrono::Duration,
+    reqwest::StatusCode,
+    yoda_taller::{
+        server::taller_route::{MatrixResponse, RankResponse, YodaTallerResponse},
+        settings::SwapiMode,
+        source::{PeopleSource, SqliteMirror, SyncReport},
+        swapi::Person,
+    },
+};
+
+#[tokio::test]
+async fn sync_only_updates_people_edited_since_the_previous_sync() {
+    let app = TestApp::spawn().await;
+    let database = tempfile::tempdir().unwrap();
+    let mirror = SqliteMirror::open(&database.path().join("people.sqlite")).unwrap();
+    let (luke, yaddle) = (people::luke(), people::yaddle());
+    app.swapi_server
+        .mock_all_people_page(
+            1,
+            swapi_mock::people_query_page_result(&[&luke, &yaddle], 2, None),
+        )
+        .await;
+
+    let report = mirror.sync(&app.swapi_client).await.unwrap();
+    assert_eq!(
+        SyncReport {
+            added: 2,
+            updated: 0,
+            unchanged: 0
+        },
+        report
+    );
+    assert!(mirror.synced_at().await.is_some());
+
+    let report = mirror.sync(&app.swapi_client).await.unwrap();
+    assert_eq!(
+        SyncReport {
+            added: 0,
+            updated: 0,
+            unchanged: 2
+        },
+        report
+    );
+
+    app.swapi_server.reset().await;
+    let edited_luke = Person {
+        name: "Luke Skywalker the Jedi".to_string(),
+        edited: luke.edited + Duration::days(1),
+        ..luke
+    };
+    app.swapi_server
+        .mock_all_people_page(
+            1,
+            swapi_mock::people_query_page_result(&[&edited_luke, &yaddle], 2, None),
+        )
+        .await;
+    let report = mirror.sync(&app.swapi_client).await.unwrap();
+    assert_eq!(
+        SyncReport {
+            added: 0,
+            updated: 1,
+            unchanged: 1
+        },
+        report
+    );
+    assert_eq!(
+        vec![edited_luke],
+        mirror.people_by_name("jedi").await.unwrap()
+    );
+}
+
+#[tokio::test]
+async fn taller_is_served_from_the_people_database_in_sqlite_mode() {
+    let database = tempfile::tempdir().unwrap();
+    let database_path = database.path().join("people.sqlite");
+    let app = TestApp::spawn_with(|settings| {
+        settings.swapi.mode = SwapiMode::Sqlite;
+        settings.swapi.database_path = Some(database_path.clone());
+    })
+    .await;
+    let yaddle = people::yaddle();
+    app.swapi_server
+        .mock_all_people_page(1, swapi_mock::person_query_result(&yaddle))
+        .await;
+    let mirror = SqliteMirror::open(&database_path).unwrap();
+    mirror.sync(&app.swapi_client).await.unwrap();
+
+    // people are not searched in Swapi
+    let response = app.send_taller_req("yad").await;
+    assert_eq!(StatusCode::OK, response.status());
+    let body: YodaTallerResponse = response.json().await.unwrap();
+    assert_eq!(yaddle.name, body.result.person);
+    assert_eq!(mirror.synced_at().await, body.synced_at);
+    assert!(body.synced_at.is_some());
+}
+
+#[tokio::test]
+async fn rank_and_matrix_tell_when_people_were_synced_in_sqlite_mode() {
+    let database = tempfile::tempdir().unwrap();
+    let database_path = database.path().join("people.sqlite");
+    let app = TestApp::spawn_with(|settings| {
+        settings.swapi.mode = SwapiMode::Sqlite;
+        settings.swapi.database_path = Some(database_path.clone());
+    })
+    .await;
+    let yaddle = people::yaddle();
+    app.swapi_server
+        .mock_all_people_page(1, swapi_mock::person_query_result(&yaddle))
+        .await;
+    let mirror = SqliteMirror::open(&database_path).unwrap();
+    mirror.sync(&app.swapi_client).await.unwrap();
+    let synced_at = mirror.synced_at().await;
+    assert!(synced_at.is_some());
+
+    let response = app.send_rank_req(&yaddle.name).await;
+    assert_eq!(StatusCode::OK, response.status());
+    let body: RankResponse = response.json().await.unwrap();
+    assert_eq!(synced_at, body.synced_at);
+
+    let response = app.send_matrix_req(&[&yaddle.name]).await;
+    assert_eq!(StatusCode::OK, response.status());
+    let body: MatrixResponse = response.json().await.unwrap();
+    assert_eq!(synced_at, body.synced_at);
+}
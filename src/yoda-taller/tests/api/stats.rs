@@ -0,0 +1,102 @@
+use {
+    crate::helpers::{people, swapi_mock, test_app::TestApp},
+    reqwest::StatusCode,
+    std::time::Duration,
+    yoda_taller::{
+        height::Height, server::stats_route::StatsErrorBody, settings::StatsSettings,
+        stats::Bucket, taller::Character,
+    },
+};
+
+#[tokio::test]
+async fn height_stats_cover_all_people_pages() {
+    let app = TestApp::spawn_with(|settings| {
+        settings.stats = StatsSettings {
+            bucket_width_cm: 50,
+            refresh_interval_milliseconds: 100,
+        }
+    })
+    .await;
+    let (yoda, luke, arvel) = (people::yoda(), people::luke(), people::arvel());
+    let (yaddle, anakin) = (people::yaddle(), people::anakin());
+    let next = Some(swapi_mock::all_people_page_url(2));
+    app.swapi_server
+        .mock_all_people_page(
+            1,
+            swapi_mock::people_query_page_result(&[&yoda, &luke, &arvel], 5, next),
+        )
+        .await;
+    app.swapi_server
+        .mock_all_people_page(
+            2,
+            swapi_mock::people_query_page_result(&[&yaddle, &anakin], 5, None),
+        )
+        .await;
+
+    let stats = app.wait_for_height_stats().await;
+    assert_eq!(4, stats.count);
+    assert_eq!(
+        Some(Character {
+            name: yaddle.name,
+            height: Height::from_cm(61)
+        }),
+        stats.min
+    );
+    assert_eq!(
+        Some(Character {
+            name: anakin.name,
+            height: Height::from_cm(188)
+        }),
+        stats.max
+    );
+    assert_eq!(Some(119.0), stats.median_cm);
+    assert_eq!(
+        vec![
+            Bucket {
+                from_cm: 50,
+                to_cm: 100,
+                count: 2
+            },
+            Bucket {
+                from_cm: 100,
+                to_cm: 150,
+                count: 0
+            },
+            Bucket {
+                from_cm: 150,
+                to_cm: 200,
+                count: 2
+            },
+        ],
+        stats.histogram
+    );
+    assert_eq!(Some(37.5), stats.yoda_percentile);
+    assert_eq!(vec![arvel.name], stats.unknown_height);
+}
+
+#[tokio::test]
+async fn return_503_if_height_stats_are_not_computed() {
+    let app = TestApp::spawn().await;
+    let response = app.send_height_stats_req().await;
+
+    assert_eq!(StatusCode::SERVICE_UNAVAILABLE, response.status());
+    assert_eq!(
+        StatsErrorBody {
+            error: "Height statistics are not computed yet".to_string()
+        },
+        response.json().await.unwrap()
+    );
+}
+
+#[tokio::test]
+async fn height_stats_are_computed_once_with_zero_refresh_interval() {
+    let app = TestApp::spawn_with(|settings| {
+        settings.stats.refresh_interval_milliseconds = 0;
+    })
+    .await;
+    tokio::time::sleep(Duration::from_millis(500)).await;
+
+    // The people are listed once, even if listing them failed.
+    let requests = app.swapi_server.received_requests().await.unwrap();
+    assert_eq!(1, requests.len());
+}
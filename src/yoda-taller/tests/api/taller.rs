@@ -4,7 +4,11 @@ use {
         test_app::{TestApp, SWAPI_TIMEOUT},
     },
     std::time::Duration,
-    yoda_taller::taller::{YodaTallerError, YodaTallerOutcome},
+    yoda_taller::{
+        height::Height,
+        ranking::MatchMode,
+        taller::{Candidate, LookupOptions, Pick, Relation, YodaTallerError, YodaTallerOutcome},
+    },
 };
 
 #[tokio::test]
@@ -13,11 +17,21 @@ async fn yoda_is_not_taller_than_luke() {
     let luke = people::luke();
     let body = swapi_mock::person_query_result(&luke);
     app.swapi_server.mock_people_query(&luke.name, body).await;
-    let is_yoda_taller = app.yoda_taller.is_taller_than(&luke.name).await.unwrap();
+    let is_yoda_taller = app
+        .yoda_taller
+        .is_taller_than(&luke.name, &LookupOptions::default())
+        .await
+        .unwrap();
     assert_eq!(
         YodaTallerOutcome {
             person: luke.name,
-            taller: false
+            taller: false,
+            relation: Relation::Shorter,
+            yoda_height: Height::from_cm(66),
+            person_height: Height::from_cm(172),
+            difference_cm: -106,
+            estimated: false,
+            basis: None
         },
         is_yoda_taller
     );
@@ -29,11 +43,21 @@ async fn yoda_is_not_taller_than_himself() {
     let yoda = people::yoda();
     let body = swapi_mock::person_query_result(&yoda);
     app.swapi_server.mock_people_query(&yoda.name, body).await;
-    let is_yoda_taller = app.yoda_taller.is_taller_than(&yoda.name).await.unwrap();
+    let is_yoda_taller = app
+        .yoda_taller
+        .is_taller_than(&yoda.name, &LookupOptions::default())
+        .await
+        .unwrap();
     assert_eq!(
         YodaTallerOutcome {
             person: yoda.name,
-            taller: false
+            taller: false,
+            relation: Relation::Equal,
+            yoda_height: Height::from_cm(66),
+            person_height: Height::from_cm(66),
+            difference_cm: 0,
+            estimated: false,
+            basis: None
         },
         is_yoda_taller
     );
@@ -45,11 +69,21 @@ async fn yoda_is_taller_than_yaddle() {
     let yaddle = people::yaddle();
     let body = swapi_mock::person_query_result(&yaddle);
     app.swapi_server.mock_people_query(&yaddle.name, body).await;
-    let is_yoda_taller = app.yoda_taller.is_taller_than(&yaddle.name).await.unwrap();
+    let is_yoda_taller = app
+        .yoda_taller
+        .is_taller_than(&yaddle.name, &LookupOptions::default())
+        .await
+        .unwrap();
     assert_eq!(
         YodaTallerOutcome {
             person: yaddle.name,
-            taller: true
+            taller: true,
+            relation: Relation::Taller,
+            yoda_height: Height::from_cm(66),
+            person_height: Height::from_cm(61),
+            difference_cm: 5,
+            estimated: false,
+            basis: None
         },
         is_yoda_taller
     );
@@ -62,7 +96,11 @@ async fn cannot_compare_yoda_and_non_existing_person() {
 
     let body = swapi_mock::empty_query_result();
     app.swapi_server.mock_people_query(name, body).await;
-    let is_taller_err = app.yoda_taller.is_taller_than(name).await.unwrap_err();
+    let is_taller_err = app
+        .yoda_taller
+        .is_taller_than(name, &LookupOptions::default())
+        .await
+        .unwrap_err();
     assert!(matches!(is_taller_err, YodaTallerError::PersonNotFound));
 }
 
@@ -74,12 +112,119 @@ async fn cannot_compare_yoda_and_person_with_invalid_height() {
     app.swapi_server.mock_people_query(&arvel.name, body).await;
     let is_taller_err = app
         .yoda_taller
-        .is_taller_than(&arvel.name)
+        .is_taller_than(&arvel.name, &LookupOptions::default())
         .await
         .unwrap_err();
     assert!(matches!(is_taller_err, YodaTallerError::HeightNotFound));
 }
 
+#[tokio::test]
+async fn cannot_compare_yoda_and_ambiguous_name() {
+    let app = TestApp::spawn().await;
+    let (luke, anakin) = (people::luke(), people::anakin());
+    let name = "sky";
+    let body = swapi_mock::people_query_page_result(&[&luke, &anakin], 2, None);
+    app.swapi_server.mock_people_query(name, body).await;
+    let is_taller_err = app
+        .yoda_taller
+        .is_taller_than(name, &LookupOptions::default())
+        .await
+        .unwrap_err();
+    match is_taller_err {
+        YodaTallerError::Ambiguous { candidates } => assert_eq!(
+            vec![
+                Candidate {
+                    id: Some(1),
+                    name: luke.name
+                },
+                Candidate {
+                    id: Some(11),
+                    name: anakin.name
+                },
+            ],
+            candidates
+        ),
+        _ => panic!("unexpected error"),
+    }
+}
+
+#[tokio::test]
+async fn first_match_is_compared_if_requested() {
+    let app = TestApp::spawn().await;
+    let (luke, anakin) = (people::luke(), people::anakin());
+    let name = "sky";
+    let body = swapi_mock::people_query_page_result(&[&luke, &anakin], 2, None);
+    app.swapi_server.mock_people_query(name, body).await;
+    let options = LookupOptions {
+        pick: Pick::First,
+        ..Default::default()
+    };
+    let is_yoda_taller = app
+        .yoda_taller
+        .is_taller_than(name, &options)
+        .await
+        .unwrap();
+    assert_eq!(
+        YodaTallerOutcome {
+            person: luke.name,
+            taller: false,
+            relation: Relation::Shorter,
+            yoda_height: Height::from_cm(66),
+            person_height: Height::from_cm(172),
+            difference_cm: -106,
+            estimated: false,
+            basis: None
+        },
+        is_yoda_taller
+    );
+}
+
+#[tokio::test]
+async fn exact_match_is_preferred() {
+    let app = TestApp::spawn().await;
+    let (luke, anakin) = (people::luke(), people::anakin());
+    let body = swapi_mock::people_query_page_result(&[&anakin, &luke], 2, None);
+    let name = "luke skywalker";
+    app.swapi_server.mock_people_query(name, body).await;
+    let is_yoda_taller = app
+        .yoda_taller
+        .is_taller_than(name, &LookupOptions::default())
+        .await
+        .unwrap();
+    assert_eq!(
+        YodaTallerOutcome {
+            person: luke.name,
+            taller: false,
+            relation: Relation::Shorter,
+            yoda_height: Height::from_cm(66),
+            person_height: Height::from_cm(172),
+            difference_cm: -106,
+            estimated: false,
+            basis: None
+        },
+        is_yoda_taller
+    );
+}
+
+#[tokio::test]
+async fn cannot_compare_yoda_and_person_not_matching_strictly() {
+    let app = TestApp::spawn().await;
+    let vader = people::vader();
+    let body = swapi_mock::person_query_result(&vader);
+    let name = "Vader";
+    app.swapi_server.mock_people_query(name, body).await;
+    let options = LookupOptions {
+        match_mode: MatchMode::Prefix,
+        ..Default::default()
+    };
+    let is_taller_err = app
+        .yoda_taller
+        .is_taller_than(name, &options)
+        .await
+        .unwrap_err();
+    assert!(matches!(is_taller_err, YodaTallerError::PersonNotFound));
+}
+
 #[tokio::test]
 async fn return_decode_error_if_invalid_response() {
     let app = TestApp::spawn().await;
@@ -89,7 +234,11 @@ async fn return_decode_error_if_invalid_response() {
         "invalid": "response"
     });
     app.swapi_server.mock_people_query(name, body).await;
-    let err = app.yoda_taller.is_taller_than(name).await.unwrap_err();
+    let err = app
+        .yoda_taller
+        .is_taller_than(name, &LookupOptions::default())
+        .await
+        .unwrap_err();
     match err {
         YodaTallerError::UnexpectedError(e) => assert!(e.is_decode()),
         _ => panic!("unexpected error"),
@@ -107,7 +256,7 @@ async fn return_timeout_error_if_timeout() {
         .await;
     let err = app
         .yoda_taller
-        .is_taller_than(&luke.name)
+        .is_taller_than(&luke.name, &LookupOptions::default())
         .await
         .unwrap_err();
     match err {
@@ -1,13 +1,17 @@
 use {
     crate::helpers::{
-        people, swapi_mock,
+        people, resources, swapi_mock,
         test_app::{TestApp, SWAPI_TIMEOUT},
     },
     reqwest::StatusCode,
     std::time::Duration,
     yoda_taller::{
-        server::taller_route::{ErrorBody, YodaTallerResponse},
-        taller::YodaTallerOutcome,
+        estimate::EstimateBasis,
+        expand::Expanded,
+        height::{Height, Unit},
+        server::taller_route::{ErrorBody, HeightsDisplay, YodaTallerResponse},
+        swapi::Species,
+        taller::{Candidate, Relation, YodaTallerOutcome},
     },
 };
 
@@ -29,8 +33,18 @@ async fn yoda_is_not_taller_than_luke() {
             query: luke.name.clone(),
             result: YodaTallerOutcome {
                 person: luke.name,
-                taller: false
-            }
+                taller: false,
+                relation: Relation::Shorter,
+                yoda_height: Height::from_cm(66),
+                person_height: Height::from_cm(172),
+                difference_cm: -106,
+                estimated: false,
+                basis: None
+            },
+            display: None,
+            expanded: None,
+            synced_at: None,
+            stale: false
         },
         body
     );
@@ -53,8 +67,18 @@ async fn yoda_is_not_taller_than_himself() {
             query: yoda.name.clone(),
             result: YodaTallerOutcome {
                 person: yoda.name,
-                taller: false
-            }
+                taller: false,
+                relation: Relation::Equal,
+                yoda_height: Height::from_cm(66),
+                person_height: Height::from_cm(66),
+                difference_cm: 0,
+                estimated: false,
+                basis: None
+            },
+            display: None,
+            expanded: None,
+            synced_at: None,
+            stale: false
         },
         body
     );
@@ -77,13 +101,82 @@ async fn yoda_is_taller_than_yaddle() {
             query: yaddle.name.clone(),
             result: YodaTallerOutcome {
                 person: yaddle.name,
-                taller: true
-            }
+                taller: true,
+                relation: Relation::Taller,
+                yoda_height: Height::from_cm(66),
+                person_height: Height::from_cm(61),
+                difference_cm: 5,
+                estimated: false,
+                basis: None
+            },
+            display: None,
+            expanded: None,
+            synced_at: None,
+            stale: false
         },
         body
     );
 }
 
+#[tokio::test]
+async fn heights_are_displayed_in_requested_units() {
+    let app = TestApp::spawn().await;
+    let luke = people::luke();
+    let query_body = swapi_mock::person_query_result(&luke);
+    app.swapi_server
+        .mock_people_query(&luke.name, query_body)
+        .await;
+    let response = app
+        .send_taller_req_with_query(&luke.name, &[("units", "ft")])
+        .await;
+    assert_eq!(StatusCode::OK, response.status());
+
+    let body: YodaTallerResponse = response.json().await.unwrap();
+    assert_eq!(
+        Some(HeightsDisplay {
+            units: Unit::Ft,
+            yoda_height: "2.17 ft".to_string(),
+            person_height: "5.64 ft".to_string(),
+            difference: "-3.48 ft".to_string()
+        }),
+        body.display
+    );
+}
+
+#[tokio::test]
+async fn return_400_if_unknown_units() {
+    let app = TestApp::spawn().await;
+    let name = "Luke Skywalker";
+    let response = app
+        .send_taller_req_with_query(name, &[("units", "parsec")])
+        .await;
+
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+    let body: ErrorBody = response.json().await.unwrap();
+    assert_eq!(name, body.query);
+    assert!(body.error.contains("unknown variant `parsec`"));
+}
+
+#[tokio::test]
+async fn return_404_if_height_is_invalid() {
+    let app = TestApp::spawn().await;
+    let (luke, yaddle) = (people::luke(), people::yaddle());
+    let mut body = swapi_mock::people_query_page_result(&[&luke, &yaddle], 2, None);
+    body["results"][0]["height"] = "tall".into();
+    app.swapi_server.mock_people_query(&luke.name, body).await;
+
+    let response = app.send_taller_req(&luke.name).await;
+    assert_eq!(StatusCode::NOT_FOUND, response.status());
+    assert_eq!(
+        ErrorBody {
+            query: luke.name,
+            error: "Person's height is unknown".to_string(),
+            candidates: vec![]
+        },
+        response.json().await.unwrap()
+    );
+}
+
 #[tokio::test]
 async fn return_404_if_spock() {
     let app = TestApp::spawn().await;
@@ -96,7 +189,8 @@ async fn return_404_if_spock() {
     assert_eq!(
         ErrorBody {
             query: name.to_string(),
-            error: "Person not found".to_string()
+            error: "Person not found".to_string(),
+            candidates: vec![]
         },
         response.json().await.unwrap()
     );
@@ -119,7 +213,227 @@ async fn return_404_if_unknown_height() {
     assert_eq!(
         ErrorBody {
             query: arvel.name,
-            error: "Person's height is unknown".to_string()
+            error: "Person's height is unknown".to_string(),
+            candidates: vec![]
+        },
+        response.json().await.unwrap()
+    );
+}
+
+#[tokio::test]
+async fn return_300_if_ambiguous_name() {
+    let app = TestApp::spawn().await;
+    let (luke, anakin) = (people::luke(), people::anakin());
+    let name = "sky";
+    let query_body = swapi_mock::people_query_page_result(&[&luke, &anakin], 2, None);
+    app.swapi_server.mock_people_query(name, query_body).await;
+    let response = app.send_taller_req(name).await;
+
+    assert_eq!(StatusCode::MULTIPLE_CHOICES, response.status());
+    assert_eq!(
+        ErrorBody {
+            query: name.to_string(),
+            error: "Multiple people match the name".to_string(),
+            candidates: vec![
+                Candidate {
+                    id: Some(1),
+                    name: luke.name
+                },
+                Candidate {
+                    id: Some(11),
+                    name: anakin.name
+                },
+            ]
+        },
+        response.json().await.unwrap()
+    );
+}
+
+#[tokio::test]
+async fn first_match_is_compared_if_requested() {
+    let app = TestApp::spawn().await;
+    let (luke, anakin) = (people::luke(), people::anakin());
+    let name = "sky";
+    let query_body = swapi_mock::people_query_page_result(&[&luke, &anakin], 2, None);
+    app.swapi_server.mock_people_query(name, query_body).await;
+    let response = app
+        .send_taller_req_with_query(name, &[("pick", "first")])
+        .await;
+    assert_eq!(StatusCode::OK, response.status());
+
+    let body = response.json().await.unwrap();
+    assert_eq!(
+        YodaTallerResponse {
+            query: name.to_string(),
+            result: YodaTallerOutcome {
+                person: luke.name,
+                taller: false,
+                relation: Relation::Shorter,
+                yoda_height: Height::from_cm(66),
+                person_height: Height::from_cm(172),
+                difference_cm: -106,
+                estimated: false,
+                basis: None
+            },
+            display: None,
+            expanded: None,
+            synced_at: None,
+            stale: false
+        },
+        body
+    );
+}
+
+#[tokio::test]
+async fn linked_resources_are_expanded_if_requested() {
+    let app = TestApp::spawn().await;
+    let yoda = people::yoda();
+    let (homeworld, species) = (resources::yodas_homeworld(), resources::yodas_species());
+    let query_body = swapi_mock::person_query_result(&yoda);
+    app.swapi_server
+        .mock_people_query(&yoda.name, query_body)
+        .await;
+    app.swapi_server
+        .mock_resource(&homeworld.url, serde_json::to_value(&homeworld).unwrap())
+        .await;
+    app.swapi_server
+        .mock_resource(&species.url, serde_json::to_value(&species).unwrap())
+        .await;
+    let response = app
+        .send_taller_req_with_query(&yoda.name, &[("expand", "homeworld,species")])
+        .await;
+    assert_eq!(StatusCode::OK, response.status());
+
+    let body = response.json().await.unwrap();
+    assert_eq!(
+        YodaTallerResponse {
+            query: yoda.name.clone(),
+            result: YodaTallerOutcome {
+                person: yoda.name,
+                taller: false,
+                relation: Relation::Equal,
+                yoda_height: Height::from_cm(66),
+                person_height: Height::from_cm(66),
+                difference_cm: 0,
+                estimated: false,
+                basis: None
+            },
+            display: None,
+            expanded: Some(Expanded {
+                homeworld: Some(homeworld),
+                species: Some(vec![species]),
+                films: None
+            }),
+            synced_at: None,
+            stale: false
+        },
+        body
+    );
+}
+
+#[tokio::test]
+async fn return_400_if_unknown_link_is_expanded() {
+    let app = TestApp::spawn().await;
+    let name = "Yoda";
+    let response = app
+        .send_taller_req_with_query(name, &[("expand", "starships")])
+        .await;
+
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+    let body: ErrorBody = response.json().await.unwrap();
+    assert_eq!(name, body.query);
+    assert!(body.error.contains("starships"), "{}", body.error);
+}
+
+#[tokio::test]
+async fn unknown_height_is_estimated_from_species_average_height() {
+    let app = TestApp::spawn().await;
+    let (arvel, human) = (people::arvel(), resources::human());
+    let query_body = swapi_mock::person_query_result(&arvel);
+    app.swapi_server
+        .mock_people_query(&arvel.name, query_body)
+        .await;
+    app.swapi_server
+        .mock_resource(&human.url, serde_json::to_value(&human).unwrap())
+        .await;
+    let response = app
+        .send_taller_req_with_query(&arvel.name, &[("estimate", "species")])
+        .await;
+    assert_eq!(StatusCode::OK, response.status());
+
+    let body: YodaTallerResponse = response.json().await.unwrap();
+    assert_eq!(
+        YodaTallerOutcome {
+            person: arvel.name,
+            taller: false,
+            relation: Relation::Shorter,
+            yoda_height: Height::from_cm(66),
+            person_height: Height::from_cm(180),
+            difference_cm: -114,
+            estimated: true,
+            basis: Some(EstimateBasis::AverageHeight {
+                species: human.name
+            })
+        },
+        body.result
+    );
+}
+
+#[tokio::test]
+async fn unknown_height_is_estimated_from_species_members() {
+    let app = TestApp::spawn().await;
+    let (arvel, luke, anakin) = (people::arvel(), people::luke(), people::anakin());
+    let human = Species {
+        average_height: None,
+        people: vec![luke.url.clone(), anakin.url.clone(), arvel.url.clone()],
+        ..resources::human()
+    };
+    let query_body = swapi_mock::person_query_result(&arvel);
+    app.swapi_server
+        .mock_people_query(&arvel.name, query_body)
+        .await;
+    app.swapi_server
+        .mock_resource(&human.url, serde_json::to_value(&human).unwrap())
+        .await;
+    for member in [&luke, &anakin, &arvel] {
+        app.swapi_server
+            .mock_resource(&member.url, serde_json::to_value(member).unwrap())
+            .await;
+    }
+    let response = app
+        .send_taller_req_with_query(&arvel.name, &[("estimate", "species")])
+        .await;
+    assert_eq!(StatusCode::OK, response.status());
+
+    let body: YodaTallerResponse = response.json().await.unwrap();
+    assert_eq!(Height::from_cm(180), body.result.person_height);
+    assert!(body.result.estimated);
+    assert_eq!(
+        Some(EstimateBasis::Members {
+            species: human.name,
+            members: 2
+        }),
+        body.result.basis
+    );
+}
+
+#[tokio::test]
+async fn return_404_if_no_exact_match() {
+    let app = TestApp::spawn().await;
+    let luke = people::luke();
+    let name = "luke";
+    let query_body = swapi_mock::person_query_result(&luke);
+    app.swapi_server.mock_people_query(name, query_body).await;
+    let response = app
+        .send_taller_req_with_query(name, &[("match", "exact")])
+        .await;
+
+    assert_eq!(StatusCode::NOT_FOUND, response.status());
+    assert_eq!(
+        ErrorBody {
+            query: name.to_string(),
+            error: "Person not found".to_string(),
+            candidates: vec![]
         },
         response.json().await.unwrap()
     );
@@ -141,7 +455,8 @@ async fn return_500_if_timeout() {
     assert_eq!(
         ErrorBody {
             query: luke.name,
-            error: "Unexpected error".to_string()
+            error: "Unexpected error".to_string(),
+            candidates: vec![]
         },
         response.json().await.unwrap()
     );